@@ -1,8 +1,9 @@
 use crate::errors::ContractError;
 use crate::events::publish_withdrawal_to_event;
 use crate::events::{
-    publish_account_initialized_event, publish_account_restricted_event,
-    publish_account_verified_event, publish_refund_processed_event, publish_token_added_event,
+    publish_account_initialized_event, publish_account_merchant_changed_event,
+    publish_account_restricted_event, publish_account_verified_event,
+    publish_refund_processed_event, publish_token_added_event,
 };
 use crate::interface::MerchantAccountTrait;
 use crate::types::{AccountInfo, DataKey, TokenBalance};
@@ -74,6 +75,36 @@ impl MerchantAccountTrait for MerchantAccount {
             .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotInitialized))
     }
 
+    fn get_manager(env: Env) -> Address {
+        get_manager(&env)
+    }
+
+    fn set_merchant(env: Env, merchant: Address) {
+        let manager = get_manager(&env);
+        manager.require_auth();
+
+        let mut account_info: AccountInfo = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AccountInfo)
+            .unwrap_or_else(|| panic_with_error!(&env, ContractError::NotInitialized));
+        let old_merchant = account_info.merchant;
+        account_info.merchant = merchant.clone();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::AccountInfo, &account_info);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Merchant, &merchant);
+        publish_account_merchant_changed_event(
+            &env,
+            old_merchant,
+            merchant,
+            env.ledger().timestamp(),
+        );
+    }
+
     fn add_token(env: Env, token: Address) {
         let manager = get_manager(&env);
         manager.require_auth();
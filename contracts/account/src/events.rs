@@ -97,3 +97,24 @@ pub struct AccountRestricted {
 pub fn publish_account_restricted_event(env: &Env, status: bool, timestamp: u64) {
     AccountRestricted { status, timestamp }.publish(env);
 }
+
+#[contractevent]
+pub struct AccountMerchantChangedEvent {
+    pub old_merchant: Address,
+    pub new_merchant: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_account_merchant_changed_event(
+    env: &Env,
+    old_merchant: Address,
+    new_merchant: Address,
+    timestamp: u64,
+) {
+    AccountMerchantChangedEvent {
+        old_merchant,
+        new_merchant,
+        timestamp,
+    }
+    .publish(env);
+}
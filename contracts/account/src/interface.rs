@@ -5,6 +5,8 @@ use soroban_sdk::{contracttrait, Address, Env, Vec};
 pub trait MerchantAccountTrait {
     fn initialize(env: Env, merchant: Address, manager: Address, merchant_id: u64);
     fn get_merchant(env: Env) -> Address;
+    fn get_manager(env: Env) -> Address;
+    fn set_merchant(env: Env, merchant: Address);
     fn add_token(env: Env, token: Address);
     fn refund(env: Env, token: Address, amount: i128, to: Address);
     fn has_token(env: Env, token: Address) -> bool;
@@ -16,6 +16,7 @@ fn test_initialize() {
     let merchant_id = 1;
     client.initialize(&merchant, &manager, &merchant_id);
     assert_eq!(client.get_merchant(), merchant);
+    assert_eq!(client.get_manager(), manager);
 }
 
 #[should_panic(expected = "HostError: Error(Contract, #1)")]
@@ -54,15 +55,15 @@ fn test_verify_account() {
     let merchant_id = 1;
     client.initialize(&merchant, &manager, &merchant_id);
 
-    assert_eq!(client.is_verified_account(), false);
+    assert!(!client.is_verified_account());
 
     client.verify_account();
     let events = env.events().all();
 
-    assert_eq!(client.is_verified_account(), true);
+    assert!(client.is_verified_account());
 
     assert!(
-        events.len() > 0,
+        !events.is_empty(),
         "No events captured immediately after verify_account!"
     );
     let (_event_contract_id, _topics, _data) = events.get(events.len() - 1).unwrap();
@@ -84,3 +85,33 @@ fn test_verify_account_unauthorized() {
     // This should fail because we're not authenticated as manager
     client.verify_account();
 }
+
+#[test]
+fn test_set_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MerchantAccount, ());
+    let client = MerchantAccountClient::new(&env, &contract_id);
+
+    let merchant = Address::generate(&env);
+    let manager = Address::generate(&env);
+    client.initialize(&merchant, &manager, &1);
+
+    let new_merchant = Address::generate(&env);
+    client.set_merchant(&new_merchant);
+    assert_eq!(client.get_merchant(), new_merchant);
+}
+
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+#[test]
+fn test_set_merchant_requires_manager() {
+    let env = Env::default();
+    let contract_id = env.register(MerchantAccount, ());
+    let client = MerchantAccountClient::new(&env, &contract_id);
+
+    let merchant = Address::generate(&env);
+    let manager = Address::generate(&env);
+    client.initialize(&merchant, &manager, &1);
+
+    client.set_merchant(&Address::generate(&env));
+}
@@ -1,4 +1,5 @@
 use crate::events;
+use crate::types::DataKey;
 use soroban_sdk::{Address, Bytes, BytesN, Env, IntoVal};
 
 pub fn deploy_account(
@@ -27,6 +28,12 @@ pub fn deploy_account(
         (merchant.clone(), manager, merchant_id).into_val(env),
     );
 
+    // Remember which merchant each genuine account was deployed for
+    env.storage().persistent().set(
+        &DataKey::DeployedAccount(deployed_contract.clone()),
+        &merchant_id,
+    );
+
     events::publish_merchant_account_deployed_event(
         env,
         merchant,
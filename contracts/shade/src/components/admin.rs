@@ -1,9 +1,12 @@
 use crate::components::{core, reentrancy};
 use crate::errors::ContractError;
 use crate::events;
-use crate::types::DataKey;
+use crate::types::{DataKey, FeeRounding, PendingFee, PendingFeeRounding, TokenDataKey};
 use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
 
+pub const FEE_CHANGE_DELAY: u64 = 86_400;
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
 pub fn add_accepted_token(env: &Env, admin: &Address, token: &Address) {
     reentrancy::enter(env);
     core::assert_admin(env, admin);
@@ -67,22 +70,227 @@ pub fn set_fee(env: &Env, admin: &Address, token: &Address, fee: i128) {
         panic_with_error!(env, ContractError::TokenNotAccepted);
     }
 
+    if !(0..=BPS_DENOMINATOR).contains(&fee) {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+
+    activate_pending_fee(env, token);
+
+    let now = env.ledger().timestamp();
+    let current_fee = get_fee(env, token);
+
+    if fee <= current_fee {
+        // Lowering the fee can only benefit payers, so it applies immediately
+        // and supersedes any scheduled increase.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingFee(token.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenFee(token.clone()), &fee);
+
+        events::publish_fee_set_event(env, token.clone(), fee, now);
+    } else {
+        let effective_at = now + FEE_CHANGE_DELAY;
+        env.storage().persistent().set(
+            &DataKey::PendingFee(token.clone()),
+            &PendingFee { fee, effective_at },
+        );
+
+        events::publish_fee_change_scheduled_event(
+            env,
+            token.clone(),
+            current_fee,
+            fee,
+            effective_at,
+            now,
+        );
+    }
+    reentrancy::exit(env);
+}
+
+pub fn cancel_fee_change(env: &Env, admin: &Address, token: &Address) {
+    reentrancy::enter(env);
+    core::assert_admin(env, admin);
+
+    activate_pending_fee(env, token);
+
+    let pending = get_pending_fee(env, token)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::NoPendingFeeChange));
+
     env.storage()
         .persistent()
-        .set(&DataKey::TokenFee(token.clone()), &fee);
+        .remove(&DataKey::PendingFee(token.clone()));
 
-    events::publish_fee_set_event(env, token.clone(), fee, env.ledger().timestamp());
+    events::publish_fee_change_cancelled_event(
+        env,
+        token.clone(),
+        pending.fee,
+        env.ledger().timestamp(),
+    );
     reentrancy::exit(env);
 }
 
 pub fn get_fee(env: &Env, token: &Address) -> i128 {
+    if let Some(pending) = get_stored_pending_fee(env, token) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            return pending.fee;
+        }
+    }
+
     env.storage()
         .persistent()
         .get(&DataKey::TokenFee(token.clone()))
         .unwrap_or(0)
 }
 
-fn get_accepted_tokens(env: &Env) -> Vec<Address> {
+pub fn get_pending_fee(env: &Env, token: &Address) -> Option<PendingFee> {
+    get_stored_pending_fee(env, token)
+        .filter(|pending| env.ledger().timestamp() < pending.effective_at)
+}
+
+/// Sets how fractional fees are rounded. Like fee increases, a mode that
+/// can only raise fees takes effect after `FEE_CHANGE_DELAY`.
+pub fn set_fee_rounding(env: &Env, admin: &Address, token: &Address, rounding: FeeRounding) {
+    reentrancy::enter(env);
+    core::assert_admin(env, admin);
+
+    if !is_accepted_token(env, token) {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
+    }
+
+    activate_pending_rounding(env, token);
+
+    let now = env.ledger().timestamp();
+    let current = get_fee_rounding(env, token);
+
+    if rounding_rank(rounding) <= rounding_rank(current) {
+        env.storage()
+            .persistent()
+            .remove(&TokenDataKey::PendingFeeRounding(token.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeRounding(token.clone()), &rounding);
+
+        events::publish_fee_rounding_set_event(env, token.clone(), rounding, now);
+    } else {
+        let effective_at = now + FEE_CHANGE_DELAY;
+        env.storage().persistent().set(
+            &TokenDataKey::PendingFeeRounding(token.clone()),
+            &PendingFeeRounding {
+                rounding,
+                effective_at,
+            },
+        );
+
+        events::publish_fee_rounding_scheduled_event(
+            env,
+            token.clone(),
+            current,
+            rounding,
+            effective_at,
+            now,
+        );
+    }
+    reentrancy::exit(env);
+}
+
+pub fn get_fee_rounding(env: &Env, token: &Address) -> FeeRounding {
+    if let Some(pending) = get_stored_pending_rounding(env, token) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            return pending.rounding;
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeRounding(token.clone()))
+        .unwrap_or(FeeRounding::Floor)
+}
+
+pub fn get_pending_fee_rounding(env: &Env, token: &Address) -> Option<PendingFeeRounding> {
+    get_stored_pending_rounding(env, token)
+        .filter(|pending| env.ledger().timestamp() < pending.effective_at)
+}
+
+// Orders rounding modes by the fee they produce: floor never exceeds
+// half-even, which never exceeds ceil.
+fn rounding_rank(rounding: FeeRounding) -> u32 {
+    match rounding {
+        FeeRounding::Floor => 0,
+        FeeRounding::HalfEven => 1,
+        FeeRounding::Ceil => 2,
+    }
+}
+
+fn get_stored_pending_rounding(env: &Env, token: &Address) -> Option<PendingFeeRounding> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::PendingFeeRounding(token.clone()))
+}
+
+fn activate_pending_rounding(env: &Env, token: &Address) {
+    if let Some(pending) = get_stored_pending_rounding(env, token) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            env.storage()
+                .persistent()
+                .set(&DataKey::FeeRounding(token.clone()), &pending.rounding);
+            env.storage()
+                .persistent()
+                .remove(&TokenDataKey::PendingFeeRounding(token.clone()));
+        }
+    }
+}
+
+/// Computes `amount * fee_bps / BPS_DENOMINATOR` with the given rounding,
+/// returning `None` on overflow.
+pub fn compute_fee(amount: i128, fee_bps: i128, rounding: FeeRounding) -> Option<i128> {
+    let numerator = amount.checked_mul(fee_bps)?;
+    let quotient = numerator / BPS_DENOMINATOR;
+    let remainder = numerator % BPS_DENOMINATOR;
+
+    let round_up = match rounding {
+        FeeRounding::Floor => false,
+        FeeRounding::Ceil => remainder > 0,
+        FeeRounding::HalfEven => {
+            let twice_remainder = remainder * 2;
+            twice_remainder > BPS_DENOMINATOR
+                || (twice_remainder == BPS_DENOMINATOR && quotient % 2 != 0)
+        }
+    };
+
+    if round_up {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+pub fn calculate_fee(env: &Env, amount: i128, token: &Address) -> i128 {
+    compute_fee(amount, get_fee(env, token), get_fee_rounding(env, token))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+}
+
+fn get_stored_pending_fee(env: &Env, token: &Address) -> Option<PendingFee> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingFee(token.clone()))
+}
+
+fn activate_pending_fee(env: &Env, token: &Address) {
+    if let Some(pending) = get_stored_pending_fee(env, token) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            env.storage()
+                .persistent()
+                .set(&DataKey::TokenFee(token.clone()), &pending.fee);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::PendingFee(token.clone()));
+        }
+    }
+}
+
+pub fn get_accepted_tokens(env: &Env) -> Vec<Address> {
     env.storage()
         .persistent()
         .get(&DataKey::AcceptedTokens)
@@ -0,0 +1,155 @@
+use crate::components::{access_control, core, merchant};
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{DataKey, MerchantApplication, MerchantProfile, Role};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub fn set_approval_required(env: &Env, admin: &Address, required: bool) {
+    core::assert_admin(env, admin);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::RegistrationApprovalRequired, &required);
+
+    events::publish_registration_approval_set_event(env, required, env.ledger().timestamp());
+}
+
+pub fn is_approval_required(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RegistrationApprovalRequired)
+        .unwrap_or(false)
+}
+
+/// Queues an application for review. Resubmitting while pending replaces
+/// the earlier application data.
+pub fn submit_application(env: &Env, applicant: &Address, profile: MerchantProfile) {
+    applicant.require_auth();
+
+    if merchant::is_merchant(env, applicant) {
+        panic_with_error!(env, ContractError::MerchantAlreadyRegistered);
+    }
+    merchant::assert_valid_profile(env, &profile);
+
+    let key = DataKey::MerchantApplication(applicant.clone());
+    if !env.storage().persistent().has(&key) {
+        add_pending_applicant(env, applicant);
+    }
+
+    let application = MerchantApplication {
+        applicant: applicant.clone(),
+        profile,
+        date_submitted: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(&key, &application);
+
+    events::publish_application_submitted_event(env, applicant.clone(), env.ledger().timestamp());
+}
+
+pub fn get_application(env: &Env, applicant: &Address) -> Option<MerchantApplication> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantApplication(applicant.clone()))
+}
+
+pub fn get_pending_merchant_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingApplicationCount)
+        .unwrap_or(0)
+}
+
+/// Returns up to `limit` pending applications starting at position `start`.
+/// Positions are not stable: deciding an application moves the last one
+/// into its place.
+pub fn get_pending_merchants(env: &Env, start: u32, limit: u32) -> Vec<MerchantApplication> {
+    let end = start
+        .saturating_add(limit)
+        .min(get_pending_merchant_count(env));
+
+    let mut applications = Vec::new(env);
+    for index in start..end {
+        if let Some(applicant) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::PendingApplication(index))
+        {
+            if let Some(application) = get_application(env, &applicant) {
+                applications.push_back(application);
+            }
+        }
+    }
+    applications
+}
+
+fn add_pending_applicant(env: &Env, applicant: &Address) {
+    let storage = env.storage().persistent();
+    let index = get_pending_merchant_count(env);
+    storage.set(&DataKey::PendingApplication(index), applicant);
+    storage.set(&DataKey::PendingApplicationIndex(applicant.clone()), &index);
+    storage.set(&DataKey::PendingApplicationCount, &(index + 1));
+}
+
+/// Swap-removes the applicant from the pending index.
+fn remove_pending_applicant(env: &Env, applicant: &Address) {
+    let storage = env.storage().persistent();
+    let index_key = DataKey::PendingApplicationIndex(applicant.clone());
+    let index: u32 = match storage.get(&index_key) {
+        Some(index) => index,
+        None => return,
+    };
+
+    let last = get_pending_merchant_count(env) - 1;
+    if index != last {
+        let moved: Address = storage.get(&DataKey::PendingApplication(last)).unwrap();
+        storage.set(&DataKey::PendingApplication(index), &moved);
+        storage.set(&DataKey::PendingApplicationIndex(moved), &index);
+    }
+    storage.remove(&DataKey::PendingApplication(last));
+    storage.remove(&index_key);
+    storage.set(&DataKey::PendingApplicationCount, &last);
+}
+
+fn take_application(env: &Env, applicant: &Address) -> MerchantApplication {
+    let application = get_application(env, applicant)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ApplicationNotFound));
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::MerchantApplication(applicant.clone()));
+    remove_pending_applicant(env, applicant);
+
+    application
+}
+
+pub fn approve_application(env: &Env, reviewer: &Address, applicant: &Address) -> u64 {
+    access_control::assert_has_role(env, reviewer, Role::Operator);
+
+    let application = take_application(env, applicant);
+
+    let merchant_id = merchant::create_merchant(env, applicant);
+    merchant::save_merchant_profile(env, merchant_id, application.profile);
+
+    events::publish_application_approved_event(
+        env,
+        applicant.clone(),
+        merchant_id,
+        reviewer.clone(),
+        env.ledger().timestamp(),
+    );
+
+    merchant_id
+}
+
+pub fn reject_application(env: &Env, reviewer: &Address, applicant: &Address) {
+    access_control::assert_has_role(env, reviewer, Role::Operator);
+
+    take_application(env, applicant);
+
+    events::publish_application_rejected_event(
+        env,
+        applicant.clone(),
+        reviewer.clone(),
+        env.ledger().timestamp(),
+    );
+}
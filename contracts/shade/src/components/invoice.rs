@@ -1,9 +1,15 @@
-use crate::components::{admin, merchant};
+use crate::components::{
+    admin, core, limits, merchant, platform, promotion, split, staff, stats, terminal,
+};
 use crate::errors::ContractError;
 use crate::events;
-use crate::types::{DataKey, Invoice, InvoiceFilter, InvoiceStatus};
+use crate::types::{
+    DataKey, FeeBearer, Invoice, InvoiceDataKey, InvoiceFee, InvoiceFilter, InvoiceStatus,
+    MerchantDataKey, MerchantRole, SignedInvoice, SplitRecipient,
+};
 use account::account::MerchantAccountClient;
-use soroban_sdk::{panic_with_error, token, Address, Env, String, Vec};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, token, Address, BytesN, Env, String, Vec};
 
 pub const MAX_REFUND_DURATION: u64 = 604_800;
 
@@ -14,21 +20,67 @@ pub fn create_invoice(
     amount: i128,
     token: &Address,
 ) -> u64 {
-    merchant_address.require_auth();
+    let (merchant_id, _) = staff::authorize(env, merchant_address, MerchantRole::Cashier);
 
+    store_new_invoice(env, merchant_id, description, amount, token)
+}
+
+/// Creates an invoice whose merchant share is split between `split`
+/// recipients on payment, with the unallocated remainder going to the
+/// merchant's account. Recipients' shares are held in escrow until the
+/// refund window closes.
+pub fn create_split_invoice(
+    env: &Env,
+    merchant_address: &Address,
+    description: &String,
+    amount: i128,
+    token: &Address,
+    split: &Vec<SplitRecipient>,
+) -> u64 {
+    let (merchant_id, _) = staff::authorize(env, merchant_address, MerchantRole::Cashier);
+    split::assert_valid_split(env, split);
+
+    let invoice_id = store_new_invoice(env, merchant_id, description, amount, token);
+    split::set_invoice_split(env, invoice_id, split);
+
+    invoice_id
+}
+
+/// Lets a platform merchant, or its cashiers, invoice on behalf of one of
+/// its sub-merchants. The invoice belongs to the sub-merchant.
+pub fn create_sub_merchant_invoice(
+    env: &Env,
+    platform: &Address,
+    sub_merchant_id: u64,
+    description: &String,
+    amount: i128,
+    token: &Address,
+) -> u64 {
+    let (platform_id, _) = staff::authorize(env, platform, MerchantRole::Cashier);
+    platform::assert_sub_merchant_of(env, platform_id, sub_merchant_id);
+
+    store_new_invoice(env, sub_merchant_id, description, amount, token)
+}
+
+fn store_new_invoice(
+    env: &Env,
+    merchant_id: u64,
+    description: &String,
+    amount: i128,
+    token: &Address,
+) -> u64 {
     if amount <= 0 {
         panic_with_error!(env, ContractError::InvalidAmount);
     }
 
-    if !merchant::is_merchant(env, merchant_address) {
-        panic_with_error!(env, ContractError::NotAuthorized);
+    merchant::assert_merchant_active(env, merchant_id);
+    merchant::assert_merchant_not_paused(env, merchant_id);
+
+    if !merchant::is_merchant_token(env, merchant_id, token) {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
     }
 
-    let merchant_id: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::MerchantId(merchant_address.clone()))
-        .unwrap();
+    limits::assert_invoice_within_limits(env, merchant_id, token, amount);
 
     let invoice_count: u64 = env
         .storage()
@@ -50,18 +102,33 @@ pub fn create_invoice(
         date_paid: None,
         amount_refunded: 0,
     };
+    let invoice_fee = InvoiceFee {
+        fee_bearer: merchant::get_fee_bearer(env, merchant_id),
+        fee_amount: 0,
+    };
 
     env.storage()
         .persistent()
         .set(&DataKey::Invoice(new_invoice_id), &invoice);
+    env.storage()
+        .persistent()
+        .set(&InvoiceDataKey::Fee(new_invoice_id), &invoice_fee);
     env.storage()
         .persistent()
         .set(&DataKey::InvoiceCount, &new_invoice_id);
 
+    let mut pending_ids = get_pending_invoice_ids(env, merchant_id);
+    pending_ids.push_back(new_invoice_id);
+    env.storage()
+        .persistent()
+        .set(&MerchantDataKey::PendingInvoices(merchant_id), &pending_ids);
+
+    stats::record_invoice_created(env, merchant_id);
+
     events::publish_invoice_created_event(
         env,
         new_invoice_id,
-        merchant_address.clone(),
+        merchant::get_merchant(env, merchant_id).address,
         amount,
         token.clone(),
     );
@@ -76,27 +143,105 @@ pub fn get_invoice(env: &Env, invoice_id: u64) -> Invoice {
         .unwrap_or_else(|| panic_with_error!(env, ContractError::InvoiceNotFound))
 }
 
-pub fn refund_invoice(env: &Env, merchant_address: &Address, invoice_id: u64) {
-    merchant_address.require_auth();
+/// Ids of the merchant's invoices that are still pending, so offboarding does
+/// not have to scan every invoice.
+pub fn get_pending_invoice_ids(env: &Env, merchant_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&MerchantDataKey::PendingInvoices(merchant_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
 
-    let invoice = get_invoice(env, invoice_id);
+fn remove_pending_invoice(env: &Env, merchant_id: u64, invoice_id: u64) {
+    let mut pending_ids = get_pending_invoice_ids(env, merchant_id);
+    if let Some(index) = pending_ids.first_index_of(invoice_id) {
+        pending_ids.remove(index);
+        env.storage()
+            .persistent()
+            .set(&MerchantDataKey::PendingInvoices(merchant_id), &pending_ids);
+    }
+}
 
-    let merchant_id: u64 = env
-        .storage()
+/// Whether the pending index and payment stats cover all of the merchant's
+/// invoices. Merchants registered before they were tracked have to be
+/// migrated with `migrate_merchant_invoices` first.
+pub fn is_invoice_index_complete(env: &Env, merchant_id: u64) -> bool {
+    env.storage()
         .persistent()
-        .get(&DataKey::MerchantId(merchant_address.clone()))
-        .unwrap_or_else(|| panic_with_error!(env, ContractError::NotAuthorized));
+        .get(&MerchantDataKey::InvoicesIndexed(merchant_id))
+        .unwrap_or(false)
+}
 
-    if invoice.merchant_id != merchant_id {
-        panic_with_error!(env, ContractError::NotAuthorized);
+/// Scans up to `limit` invoices for a legacy merchant, resuming where the
+/// previous call stopped, and adds its pending invoices to the index and its
+/// payments to its stats. Returns true once every invoice has been scanned.
+pub fn migrate_merchant_invoices(env: &Env, admin: &Address, merchant_id: u64, limit: u32) -> bool {
+    core::assert_admin(env, admin);
+    merchant::get_merchant(env, merchant_id);
+
+    if is_invoice_index_complete(env, merchant_id) {
+        return true;
     }
 
+    let storage = env.storage().persistent();
+    let invoice_count: u64 = storage.get(&DataKey::InvoiceCount).unwrap_or(0);
+    let cursor_key = MerchantDataKey::InvoiceIndexCursor(merchant_id);
+    let start_id: u64 = storage.get(&cursor_key).unwrap_or(1);
+    let end_id = start_id
+        .saturating_add(limit as u64)
+        .saturating_sub(1)
+        .min(invoice_count);
+
+    let mut pending_ids = get_pending_invoice_ids(env, merchant_id);
+    for invoice_id in start_id..=end_id {
+        let Some(invoice) = storage.get::<_, Invoice>(&DataKey::Invoice(invoice_id)) else {
+            continue;
+        };
+        if invoice.merchant_id != merchant_id {
+            continue;
+        }
+
+        if invoice.status == InvoiceStatus::Pending && !pending_ids.contains(invoice_id) {
+            pending_ids.push_back(invoice_id);
+        }
+        if let Some(date_paid) = invoice.date_paid {
+            stats::record_legacy_payment(env, merchant_id, date_paid);
+        }
+    }
+    storage.set(&MerchantDataKey::PendingInvoices(merchant_id), &pending_ids);
+
+    if end_id >= invoice_count {
+        storage.remove(&cursor_key);
+        storage.set(&MerchantDataKey::InvoicesIndexed(merchant_id), &true);
+        true
+    } else {
+        storage.set(&cursor_key, &(end_id + 1));
+        false
+    }
+}
+
+/// Invoices created before fee bearers existed have no stored fee terms and
+/// were always merchant-pays.
+pub fn get_invoice_fee(env: &Env, invoice_id: u64) -> InvoiceFee {
+    get_invoice(env, invoice_id);
+    env.storage()
+        .persistent()
+        .get(&InvoiceDataKey::Fee(invoice_id))
+        .unwrap_or(InvoiceFee {
+            fee_bearer: FeeBearer::Merchant,
+            fee_amount: 0,
+        })
+}
+
+pub fn refund_invoice(env: &Env, caller: &Address, invoice_id: u64) {
+    let invoice = get_invoice(env, invoice_id);
+
     let amount_to_refund = invoice.amount - invoice.amount_refunded;
     if amount_to_refund <= 0 {
         panic_with_error!(env, ContractError::InvalidAmount);
     }
 
-    refund_invoice_partial(env, invoice_id, amount_to_refund);
+    refund_invoice_partial(env, caller, invoice_id, amount_to_refund);
 }
 
 pub fn get_invoices(env: &Env, filter: InvoiceFilter) -> Vec<Invoice> {
@@ -157,17 +302,35 @@ pub fn get_invoices(env: &Env, filter: InvoiceFilter) -> Vec<Invoice> {
     invoices
 }
 
-pub fn refund_invoice_partial(env: &Env, invoice_id: u64, amount: i128) {
+pub fn refund_invoice_partial(env: &Env, caller: &Address, invoice_id: u64, amount: i128) {
     let mut invoice = get_invoice(env, invoice_id);
 
-    let merchant_address = merchant::get_merchant(env, invoice.merchant_id).address;
-    merchant_address.require_auth();
+    let (merchant_id, refund_limit) = staff::authorize(env, caller, MerchantRole::RefundManager);
+    if invoice.merchant_id != merchant_id {
+        panic_with_error!(env, ContractError::NotAuthorized);
+    }
+    if let Some(refund_limit) = refund_limit {
+        staff::record_refund(env, caller, invoice_id, amount, refund_limit);
+    }
+
+    let merchant_data = merchant::get_merchant(env, invoice.merchant_id);
+    let merchant_address = merchant_data.address;
+
+    if !merchant_data.active {
+        panic_with_error!(env, ContractError::MerchantInactive);
+    }
 
     if invoice.status != InvoiceStatus::Paid && invoice.status != InvoiceStatus::PartiallyRefunded {
         panic_with_error!(env, ContractError::InvalidInvoiceStatus);
     }
 
-    if amount <= 0 || invoice.amount_refunded + amount > invoice.amount {
+    // Refunds are capped at the invoice amount; the protocol fee is retained
+    // regardless of which party bore it.
+    let total_refunded = invoice
+        .amount_refunded
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    if amount <= 0 || total_refunded > invoice.amount {
         panic_with_error!(env, ContractError::InvalidAmount);
     }
 
@@ -183,15 +346,28 @@ pub fn refund_invoice_partial(env: &Env, invoice_id: u64, amount: i128) {
         .payer
         .clone()
         .unwrap_or_else(|| panic_with_error!(env, ContractError::InvalidInvoiceStatus));
-    let merchant_account: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::MerchantBalance(merchant_address.clone()))
-        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantAccountNotFound));
+    let merchant_account = merchant::get_merchant_account(env, invoice.merchant_id);
     let token = invoice.token.clone();
-    MerchantAccountClient::new(env, &merchant_account).refund(&token, &amount, &payer);
 
-    invoice.amount_refunded += amount;
+    // The platform returns its commission and split recipients their
+    // escrowed share; the merchant covers the rest
+    let commission_refund = platform::refund_commission(
+        env,
+        invoice_id,
+        &token,
+        &payer,
+        invoice.amount,
+        invoice.amount_refunded,
+        total_refunded,
+    );
+    let split_refund = split::refund_split(env, &invoice, &payer, total_refunded);
+    let merchant_refund = amount - commission_refund - split_refund;
+    if merchant_refund > 0 {
+        MerchantAccountClient::new(env, &merchant_account).refund(&token, &merchant_refund, &payer);
+    }
+
+    let previous_status = invoice.status;
+    invoice.amount_refunded = total_refunded;
     let is_fully_refunded = invoice.amount_refunded == invoice.amount;
     invoice.status = if is_fully_refunded {
         InvoiceStatus::Refunded
@@ -203,8 +379,29 @@ pub fn refund_invoice_partial(env: &Env, invoice_id: u64, amount: i128) {
         .persistent()
         .set(&DataKey::Invoice(invoice_id), &invoice);
 
+    limits::record_refund(env, invoice.merchant_id, &token, amount, date_paid);
+
+    stats::record_invoice_refunded(
+        env,
+        invoice.merchant_id,
+        &token,
+        amount,
+        previous_status,
+        invoice.status,
+    );
+
+    // The retained protocol fee is reported so indexers can reconcile refunds
+    let invoice_fee = get_invoice_fee(env, invoice_id);
     if is_fully_refunded {
-        events::publish_invoice_refunded_event(env, invoice_id, merchant_address, amount, now);
+        events::publish_invoice_refunded_event(
+            env,
+            invoice_id,
+            merchant_address,
+            amount,
+            invoice_fee.fee_amount,
+            invoice_fee.fee_bearer,
+            now,
+        );
     } else {
         events::publish_invoice_partially_refunded_event(
             env,
@@ -212,34 +409,87 @@ pub fn refund_invoice_partial(env: &Env, invoice_id: u64, amount: i128) {
             merchant_address,
             amount,
             invoice.amount_refunded,
+            invoice_fee.fee_amount,
+            invoice_fee.fee_bearer,
             now,
         );
     }
 }
 
 pub fn pay_invoice(env: &Env, payer: &Address, invoice_id: u64) {
+    pay_invoice_with_limits(env, payer, invoice_id, None, None);
+}
+
+pub fn pay_invoice_with_limits(
+    env: &Env,
+    payer: &Address,
+    invoice_id: u64,
+    max_total: Option<i128>,
+    max_fee: Option<i128>,
+) {
     payer.require_auth();
 
     // Get invoice
     let mut invoice = get_invoice(env, invoice_id);
+    let mut invoice_fee = get_invoice_fee(env, invoice_id);
 
     // Check invoice status
     if invoice.status != InvoiceStatus::Pending {
         panic_with_error!(env, ContractError::InvalidInvoiceStatus);
     }
 
-    // Check token is accepted
-    if !admin::is_accepted_token(env, &invoice.token) {
+    // Pending invoices of a deactivated merchant stay pending but cannot be
+    // paid until the merchant is reactivated; the merchant may still void them.
+    merchant::assert_merchant_active(env, invoice.merchant_id);
+    merchant::assert_merchant_not_paused(env, invoice.merchant_id);
+
+    // Check token is still accepted, globally and by the merchant
+    if !merchant::is_merchant_token(env, invoice.merchant_id, &invoice.token) {
         panic_with_error!(env, ContractError::TokenNotAccepted);
     }
 
-    // Get fee in basis points (e.g., 500 = 5%)
-    let fee_bps = admin::get_fee(env, &invoice.token);
+    // Tier limits may have tightened since the invoice was created
+    limits::record_payment(env, invoice.merchant_id, &invoice.token, invoice.amount);
+
+    // Volume covered by a promotional fee holiday is exempt from the fee
+    let waived_amount =
+        promotion::apply_promotion(env, invoice.merchant_id, &invoice.token, invoice.amount);
+
+    // Fee in basis points (e.g., 500 = 5%), rounded per the token's policy
+    let fee_amount = admin::calculate_fee(env, invoice.amount - waived_amount, &invoice.token);
+
+    // In payer-pays mode the fee is charged on top of the invoice amount,
+    // otherwise it is deducted from the merchant's share.
+    let (total_paid, merchant_amount) = match invoice_fee.fee_bearer {
+        FeeBearer::Merchant => (invoice.amount, invoice.amount - fee_amount),
+        FeeBearer::Payer => (
+            invoice
+                .amount
+                .checked_add(fee_amount)
+                .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow)),
+            invoice.amount,
+        ),
+    };
 
-    // Calculate fee and merchant amount
-    // fee = (amount * fee_bps) / 10000
-    let fee_amount = (invoice.amount * fee_bps) / 10000;
-    let merchant_amount = invoice.amount - fee_amount;
+    // Revert if the fee or the amount moved beyond what the payer agreed to
+    if let Some(max_total) = max_total {
+        if total_paid > max_total {
+            panic_with_error!(env, ContractError::PaymentLimitExceeded);
+        }
+    }
+    if let Some(max_fee) = max_fee {
+        if fee_amount > max_fee {
+            panic_with_error!(env, ContractError::PaymentLimitExceeded);
+        }
+    }
+
+    // A platform's commission comes out of its sub-merchant's share
+    let commission =
+        platform::calculate_commission(env, invoice.merchant_id, invoice.amount, merchant_amount);
+    let merchant_amount = match commission {
+        Some((_, commission_amount)) => merchant_amount - commission_amount,
+        None => merchant_amount,
+    };
 
     // Get merchant account address
     let merchant_account = merchant::get_merchant_account(env, invoice.merchant_id);
@@ -253,19 +503,51 @@ pub fn pay_invoice(env: &Env, payer: &Address, invoice_id: u64) {
         token_client.transfer(payer, &shade_contract, &fee_amount);
     }
 
+    // Split recipients are paid first; the merchant keeps the remainder
+    let merchant_amount = split::pay_split(env, invoice_id, &invoice.token, payer, merchant_amount);
+
     // Transfer merchant amount to merchant account
     if merchant_amount > 0 {
         token_client.transfer(payer, &merchant_account, &merchant_amount);
     }
 
+    // Transfer commission to the platform's merchant account
+    if let Some((platform_id, commission_amount)) = commission {
+        if commission_amount > 0 {
+            let platform_account = merchant::get_merchant_account(env, platform_id);
+            token_client.transfer(payer, &platform_account, &commission_amount);
+            platform::record_commission(env, invoice_id, platform_id, commission_amount);
+            events::publish_platform_commission_paid_event(
+                env,
+                invoice_id,
+                platform_id,
+                commission_amount,
+                env.ledger().timestamp(),
+            );
+        }
+    }
+
     // Update invoice
     invoice.status = InvoiceStatus::Paid;
     invoice.payer = Some(payer.clone());
     invoice.date_paid = Some(env.ledger().timestamp());
+    invoice_fee.fee_amount = fee_amount;
 
     env.storage()
         .persistent()
         .set(&DataKey::Invoice(invoice_id), &invoice);
+    env.storage()
+        .persistent()
+        .set(&InvoiceDataKey::Fee(invoice_id), &invoice_fee);
+    remove_pending_invoice(env, invoice.merchant_id, invoice_id);
+
+    stats::record_invoice_paid(
+        env,
+        invoice.merchant_id,
+        &invoice.token,
+        invoice.amount,
+        fee_amount,
+    );
 
     // Emit event
     events::publish_invoice_paid_event(
@@ -275,22 +557,54 @@ pub fn pay_invoice(env: &Env, payer: &Address, invoice_id: u64) {
         invoice.amount,
         fee_amount,
         merchant_amount,
+        total_paid,
+        invoice_fee.fee_bearer,
         env.ledger().timestamp(),
     );
 }
 
-pub fn void_invoice(env: &Env, merchant_address: &Address, invoice_id: u64) {
-    merchant_address.require_auth();
+/// Settles an invoice issued off-chain by a point-of-sale terminal. The
+/// payload is signed over the Shade contract address and the invoice, and
+/// each terminal nonce can only be used once.
+pub fn pay_signed_invoice(
+    env: &Env,
+    payer: &Address,
+    signed_invoice: &SignedInvoice,
+    terminal_key: &BytesN<32>,
+    signature: &BytesN<64>,
+) -> u64 {
+    if !terminal::is_terminal_key(env, signed_invoice.merchant_id, terminal_key) {
+        panic_with_error!(env, ContractError::TerminalKeyNotFound);
+    }
+
+    if env.ledger().timestamp() > signed_invoice.expires_at {
+        panic_with_error!(env, ContractError::SignedInvoiceExpired);
+    }
+
+    let message = (env.current_contract_address(), signed_invoice.clone()).to_xdr(env);
+    env.crypto()
+        .ed25519_verify(terminal_key, &message, signature);
+
+    terminal::use_nonce(env, terminal_key, signed_invoice.nonce);
 
+    let invoice_id = store_new_invoice(
+        env,
+        signed_invoice.merchant_id,
+        &signed_invoice.description,
+        signed_invoice.amount,
+        &signed_invoice.token,
+    );
+    pay_invoice(env, payer, invoice_id);
+
+    invoice_id
+}
+
+pub fn void_invoice(env: &Env, merchant_address: &Address, invoice_id: u64) {
     // Get invoice
     let mut invoice = get_invoice(env, invoice_id);
 
-    // Get merchant ID for ownership check
-    let merchant_id: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::MerchantId(merchant_address.clone()))
-        .unwrap_or_else(|| panic_with_error!(env, ContractError::NotAuthorized));
+    // Cashiers may void on the merchant's behalf
+    let (merchant_id, _) = staff::authorize(env, merchant_address, MerchantRole::Cashier);
 
     // Verify merchant owns this invoice
     if invoice.merchant_id != merchant_id {
@@ -309,12 +623,14 @@ pub fn void_invoice(env: &Env, merchant_address: &Address, invoice_id: u64) {
     env.storage()
         .persistent()
         .set(&DataKey::Invoice(invoice_id), &invoice);
+    remove_pending_invoice(env, merchant_id, invoice_id);
+    stats::record_invoice_cancelled(env, merchant_id);
 
     // Emit event
     events::publish_invoice_cancelled_event(
         env,
         invoice_id,
-        merchant_address.clone(),
+        merchant::get_merchant(env, merchant_id).address,
         env.ledger().timestamp(),
     );
 }
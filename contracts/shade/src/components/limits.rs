@@ -0,0 +1,174 @@
+use crate::components::{admin, core, merchant};
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{DataKey, LimitHeadroom, TierLimits, VerificationTier, VolumeWindow};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub const LIMIT_DAY: u64 = 86_400;
+pub const LIMIT_WINDOW_DAYS: u64 = 30;
+
+pub fn set_tier_limits(
+    env: &Env,
+    admin: &Address,
+    tier: VerificationTier,
+    token: &Address,
+    limits: &TierLimits,
+) {
+    core::assert_admin(env, admin);
+
+    if !admin::is_accepted_token(env, token) {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
+    }
+
+    for limit in [limits.max_invoice_amount, limits.max_volume_30d]
+        .into_iter()
+        .flatten()
+    {
+        if limit <= 0 {
+            panic_with_error!(env, ContractError::InvalidAmount);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::TierLimits(tier, token.clone()), limits);
+
+    events::publish_tier_limits_set_event(
+        env,
+        tier,
+        token.clone(),
+        limits.max_invoice_amount,
+        limits.max_volume_30d,
+        env.ledger().timestamp(),
+    );
+}
+
+/// Tiers without configured limits are unrestricted.
+pub fn get_tier_limits(env: &Env, tier: VerificationTier, token: &Address) -> TierLimits {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TierLimits(tier, token.clone()))
+        .unwrap_or(TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: None,
+        })
+}
+
+/// Paid volume over the current day and the 29 before it.
+pub fn get_rolling_volume(env: &Env, merchant_id: u64, token: &Address) -> i128 {
+    let mut volume: i128 = 0;
+    for day_volume in load_window(env, merchant_id, token).daily.iter() {
+        volume = volume.saturating_add(day_volume);
+    }
+    volume
+}
+
+/// Loads the merchant's volume window with the buckets of days that have
+/// passed since it was last written cleared.
+fn load_window(env: &Env, merchant_id: u64, token: &Address) -> VolumeWindow {
+    let today = env.ledger().timestamp() / LIMIT_DAY;
+    let mut window = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantVolume(merchant_id, token.clone()))
+        .unwrap_or(VolumeWindow {
+            last_day: today,
+            daily: Vec::from_array(env, [0; LIMIT_WINDOW_DAYS as usize]),
+        });
+
+    if today >= window.last_day.saturating_add(LIMIT_WINDOW_DAYS) {
+        window.daily = Vec::from_array(env, [0; LIMIT_WINDOW_DAYS as usize]);
+    } else {
+        for day in window.last_day + 1..=today {
+            window.daily.set(bucket(day), 0);
+        }
+    }
+    window.last_day = window.last_day.max(today);
+    window
+}
+
+fn bucket(day: u64) -> u32 {
+    (day % LIMIT_WINDOW_DAYS) as u32
+}
+
+pub fn get_limit_headroom(env: &Env, merchant_id: u64, token: &Address) -> LimitHeadroom {
+    let tier = merchant::get_merchant_tier(env, merchant_id);
+    let limits = get_tier_limits(env, tier, token);
+
+    let volume_remaining = limits.max_volume_30d.map(|cap| {
+        cap.saturating_sub(get_rolling_volume(env, merchant_id, token))
+            .max(0)
+    });
+
+    LimitHeadroom {
+        tier,
+        max_invoice_amount: limits.max_invoice_amount,
+        volume_remaining,
+    }
+}
+
+pub fn assert_invoice_within_limits(env: &Env, merchant_id: u64, token: &Address, amount: i128) {
+    let tier = merchant::get_merchant_tier(env, merchant_id);
+    let limits = get_tier_limits(env, tier, token);
+
+    if let Some(max_invoice_amount) = limits.max_invoice_amount {
+        if amount > max_invoice_amount {
+            panic_with_error!(env, ContractError::MerchantLimitExceeded);
+        }
+    }
+}
+
+/// Checks the payment against the merchant's tier limits and counts it
+/// towards the rolling volume.
+pub fn record_payment(env: &Env, merchant_id: u64, token: &Address, amount: i128) {
+    assert_invoice_within_limits(env, merchant_id, token, amount);
+
+    let mut window = load_window(env, merchant_id, token);
+    let mut volume: i128 = 0;
+    for day_volume in window.daily.iter() {
+        volume = volume.saturating_add(day_volume);
+    }
+
+    let tier = merchant::get_merchant_tier(env, merchant_id);
+    if let Some(cap) = get_tier_limits(env, tier, token).max_volume_30d {
+        let volume = volume
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+        if volume > cap {
+            panic_with_error!(env, ContractError::MerchantLimitExceeded);
+        }
+    }
+
+    let index = bucket(window.last_day);
+    let day_volume = window
+        .daily
+        .get(index)
+        .unwrap_or(0)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    window.daily.set(index, day_volume);
+    save_window(env, merchant_id, token, &window);
+}
+
+/// Takes refunded volume back out of the day the invoice was paid, if that
+/// day is still within the window.
+pub fn record_refund(env: &Env, merchant_id: u64, token: &Address, amount: i128, paid_at: u64) {
+    let mut window = load_window(env, merchant_id, token);
+    let paid_day = paid_at / LIMIT_DAY;
+    if paid_day > window.last_day || window.last_day - paid_day >= LIMIT_WINDOW_DAYS {
+        return;
+    }
+
+    let index = bucket(paid_day);
+    let day_volume = window.daily.get(index).unwrap_or(0);
+    window
+        .daily
+        .set(index, day_volume.saturating_sub(amount).max(0));
+    save_window(env, merchant_id, token, &window);
+}
+
+fn save_window(env: &Env, merchant_id: u64, token: &Address, window: &VolumeWindow) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantVolume(merchant_id, token.clone()), window);
+}
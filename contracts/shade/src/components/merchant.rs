@@ -1,12 +1,33 @@
-use crate::components::core;
+use crate::components::{
+    access_control, account_factory, admin, application, core, invoice, platform, stake, stats,
+};
 use crate::errors::ContractError;
 use crate::events;
-use crate::types::{DataKey, Merchant, MerchantFilter};
-use soroban_sdk::{panic_with_error, Address, BytesN, Env, Vec};
+use crate::types::{
+    DataKey, FeeBearer, InvoiceStatus, Merchant, MerchantDataKey, MerchantFilter,
+    MerchantKeyRecord, MerchantPause, MerchantProfile, Role, VerificationTier,
+};
+use account::account::MerchantAccountClient;
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, String, Vec};
+
+pub const MAX_PROFILE_NAME_LEN: u32 = 64;
+pub const MAX_PROFILE_WEBSITE_LEN: u32 = 128;
+pub const MAX_PROFILE_LOGO_URI_LEN: u32 = 256;
+pub const MAX_PROFILE_CATEGORY_LEN: u32 = 32;
+pub const COUNTRY_CODE_LEN: u32 = 2;
+pub const MAX_PAUSE_MESSAGE_LEN: u32 = 256;
 
 pub fn register_merchant(env: &Env, merchant: &Address) {
     merchant.require_auth();
 
+    if application::is_approval_required(env) {
+        panic_with_error!(env, ContractError::RegistrationRequiresApproval);
+    }
+
+    create_merchant(env, merchant);
+}
+
+pub fn create_merchant(env: &Env, merchant: &Address) -> u64 {
     if env
         .storage()
         .persistent()
@@ -40,6 +61,11 @@ pub fn register_merchant(env: &Env, merchant: &Address) {
     env.storage()
         .persistent()
         .set(&DataKey::MerchantCount, &new_id);
+    // Invoices of new merchants are tracked in stats and the pending index
+    // from the start
+    env.storage()
+        .persistent()
+        .set(&MerchantDataKey::InvoicesIndexed(new_id), &true);
 
     events::publish_merchant_registered_event(
         env,
@@ -47,6 +73,8 @@ pub fn register_merchant(env: &Env, merchant: &Address) {
         new_id,
         env.ledger().timestamp(),
     );
+
+    new_id
 }
 
 pub fn get_merchant(env: &Env, merchant_id: u64) -> Merchant {
@@ -70,6 +98,24 @@ pub fn get_merchant(env: &Env, merchant_id: u64) -> Merchant {
         .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound))
 }
 
+pub fn get_merchant_id(env: &Env, merchant: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound))
+}
+
+pub fn get_merchant_by_address(env: &Env, merchant: &Address) -> Merchant {
+    get_merchant(env, get_merchant_id(env, merchant))
+}
+
+pub fn get_merchant_count(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantCount)
+        .unwrap_or(0)
+}
+
 pub fn is_merchant(env: &Env, merchant: &Address) -> bool {
     env.storage()
         .persistent()
@@ -99,12 +145,20 @@ pub fn set_merchant_status(env: &Env, admin: &Address, merchant_id: u64, status:
         .get(&DataKey::Merchant(merchant_id))
         .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
 
+    if is_merchant_closed(env, merchant_id) {
+        panic_with_error!(env, ContractError::MerchantClosed);
+    }
+
     merchant.active = status;
 
     env.storage()
         .persistent()
         .set(&DataKey::Merchant(merchant_id), &merchant);
 
+    if let Some(account) = get_managed_account(env, merchant_id) {
+        MerchantAccountClient::new(env, &account).restrict_account(&!status);
+    }
+
     events::publish_merchant_status_changed_event(
         env,
         merchant_id,
@@ -113,6 +167,16 @@ pub fn set_merchant_status(env: &Env, admin: &Address, merchant_id: u64, status:
     );
 }
 
+/// Closure is kept under its own key so merchant records stored before
+/// merchants could be closed still decode.
+pub fn is_merchant_closed(env: &Env, merchant_id: u64) -> bool {
+    get_merchant(env, merchant_id);
+    env.storage()
+        .persistent()
+        .get(&MerchantDataKey::Closed(merchant_id))
+        .unwrap_or(false)
+}
+
 pub fn is_merchant_active(env: &Env, merchant_id: u64) -> bool {
     if merchant_id == 0 {
         panic_with_error!(env, ContractError::MerchantNotFound);
@@ -137,9 +201,97 @@ pub fn is_merchant_active(env: &Env, merchant_id: u64) -> bool {
     merchant.active
 }
 
+pub fn assert_merchant_active(env: &Env, merchant_id: u64) {
+    if !get_merchant(env, merchant_id).active {
+        panic_with_error!(env, ContractError::MerchantInactive);
+    }
+}
+
+/// Lets a merchant stop taking new invoices and payments on its own, e.g.
+/// while on vacation. Without `resume_at` the pause lasts until
+/// `resume_merchant` is called.
+pub fn pause_merchant(env: &Env, merchant: &Address, resume_at: Option<u64>, message: String) {
+    merchant.require_auth();
+
+    let merchant_id = get_merchant_id(env, merchant);
+    let now = env.ledger().timestamp();
+
+    if resume_at.is_some_and(|at| at <= now) || message.len() > MAX_PAUSE_MESSAGE_LEN {
+        panic_with_error!(env, ContractError::InvalidMerchantPause);
+    }
+
+    env.storage().persistent().set(
+        &DataKey::MerchantPause(merchant_id),
+        &MerchantPause {
+            paused_at: now,
+            resume_at,
+            message: message.clone(),
+        },
+    );
+
+    events::publish_merchant_paused_event(env, merchant_id, resume_at, message, now);
+}
+
+pub fn resume_merchant(env: &Env, merchant: &Address) {
+    merchant.require_auth();
+
+    let merchant_id = get_merchant_id(env, merchant);
+    if !is_merchant_paused(env, merchant_id) {
+        panic_with_error!(env, ContractError::MerchantNotPaused);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::MerchantPause(merchant_id));
+
+    events::publish_merchant_resumed_event(env, merchant_id, env.ledger().timestamp());
+}
+
+/// Returns the merchant's pause while it is in effect; a pause whose
+/// `resume_at` has passed is treated as lifted.
+pub fn get_merchant_pause(env: &Env, merchant_id: u64) -> Option<MerchantPause> {
+    let pause: MerchantPause = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantPause(merchant_id))?;
+
+    match pause.resume_at {
+        Some(resume_at) if env.ledger().timestamp() >= resume_at => None,
+        _ => Some(pause),
+    }
+}
+
+pub fn is_merchant_paused(env: &Env, merchant_id: u64) -> bool {
+    get_merchant_pause(env, merchant_id).is_some()
+}
+
+pub fn assert_merchant_not_paused(env: &Env, merchant_id: u64) {
+    if is_merchant_paused(env, merchant_id) {
+        panic_with_error!(env, ContractError::MerchantPaused);
+    }
+}
+
 pub fn verify_merchant(env: &Env, admin: &Address, merchant_id: u64, status: bool) {
     core::assert_admin(env, admin);
 
+    // Verifying an unverified merchant grants the basic tier; revoking
+    // drops any explicit tier.
+    if !status || get_merchant_tier(env, merchant_id) == VerificationTier::Unverified {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MerchantTier(merchant_id));
+    }
+
+    store_verified(env, merchant_id, status);
+
+    events::publish_merchant_verified_event(env, merchant_id, status, env.ledger().timestamp());
+}
+
+fn store_verified(env: &Env, merchant_id: u64, status: bool) {
+    if status && !stake::has_required_stake(env, merchant_id) {
+        panic_with_error!(env, ContractError::StakeRequired);
+    }
+
     let mut merchant_data = get_merchant(env, merchant_id);
     merchant_data.verified = status;
 
@@ -147,7 +299,38 @@ pub fn verify_merchant(env: &Env, admin: &Address, merchant_id: u64, status: boo
         .persistent()
         .set(&DataKey::Merchant(merchant_id), &merchant_data);
 
-    events::publish_merchant_verified_event(env, merchant_id, status, env.ledger().timestamp());
+    // Accounts only support being verified, so revocation is not propagated
+    if status {
+        if let Some(account) = get_managed_account(env, merchant_id) {
+            MerchantAccountClient::new(env, &account).verify_account();
+        }
+    }
+}
+
+pub fn set_merchant_tier(env: &Env, admin: &Address, merchant_id: u64, tier: VerificationTier) {
+    core::assert_admin(env, admin);
+    get_merchant(env, merchant_id);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantTier(merchant_id), &tier);
+    store_verified(env, merchant_id, tier != VerificationTier::Unverified);
+
+    events::publish_merchant_tier_set_event(env, merchant_id, tier, env.ledger().timestamp());
+}
+
+/// Merchants without an explicit tier are `Basic` once verified.
+pub fn get_merchant_tier(env: &Env, merchant_id: u64) -> VerificationTier {
+    let merchant_data = get_merchant(env, merchant_id);
+
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantTier(merchant_id))
+        .unwrap_or(if merchant_data.verified {
+            VerificationTier::Basic
+        } else {
+            VerificationTier::Unverified
+        })
 }
 
 pub fn is_merchant_verified(env: &Env, merchant_id: u64) -> bool {
@@ -155,22 +338,268 @@ pub fn is_merchant_verified(env: &Env, merchant_id: u64) -> bool {
     merchant_data.verified
 }
 
-pub fn set_merchant_key(env: &Env, merchant: &Address, key: &BytesN<32>) {
+fn propose_merchant_address(env: &Env, merchant_id: u64, new_address: &Address) {
+    if is_merchant(env, new_address) {
+        panic_with_error!(env, ContractError::MerchantAlreadyRegistered);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingMerchantAddress(merchant_id), new_address);
+
+    events::publish_merchant_address_proposed_event(
+        env,
+        merchant_id,
+        new_address.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+/// First step of an address rotation, signed by the current address.
+pub fn change_merchant_address(env: &Env, merchant: &Address, new_address: &Address) {
     merchant.require_auth();
 
-    if !is_merchant(env, merchant) {
-        panic_with_error!(env, ContractError::MerchantNotFound);
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    propose_merchant_address(env, merchant_id, new_address);
+}
+
+/// Recovery path for merchants that lost control of their current address.
+/// The new address still has to accept the change itself.
+pub fn recover_merchant_address(
+    env: &Env,
+    admin: &Address,
+    merchant_id: u64,
+    new_address: &Address,
+) {
+    core::assert_admin(env, admin);
+    if is_merchant_closed(env, merchant_id) {
+        panic_with_error!(env, ContractError::MerchantClosed);
+    }
+
+    propose_merchant_address(env, merchant_id, new_address);
+}
+
+pub fn accept_merchant_address(env: &Env, new_address: &Address, merchant_id: u64) {
+    new_address.require_auth();
+
+    let pending: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PendingMerchantAddress(merchant_id));
+    if pending.as_ref() != Some(new_address) {
+        panic_with_error!(env, ContractError::NoPendingAddressChange);
     }
 
+    // A closed merchant's id must never be bound to an address again
+    if is_merchant_closed(env, merchant_id) {
+        panic_with_error!(env, ContractError::MerchantClosed);
+    }
+
+    // The address may have registered on its own since the proposal
+    if is_merchant(env, new_address) {
+        panic_with_error!(env, ContractError::MerchantAlreadyRegistered);
+    }
+
+    let mut merchant_data = get_merchant(env, merchant_id);
+    let old_address = merchant_data.address.clone();
+    merchant_data.address = new_address.clone();
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::Merchant(merchant_id), &merchant_data);
+    storage.remove(&DataKey::MerchantId(old_address.clone()));
+    storage.set(&DataKey::MerchantId(new_address.clone()), &merchant_id);
+    storage.remove(&DataKey::PendingMerchantAddress(merchant_id));
+
+    if let Some(key) = storage.get::<_, BytesN<32>>(&DataKey::MerchantKey(old_address.clone())) {
+        storage.remove(&DataKey::MerchantKey(old_address.clone()));
+        storage.set(&DataKey::MerchantKey(new_address.clone()), &key);
+    }
+
+    // Accounts not yet migrated are still keyed by the merchant address
+    if let Some(account) = storage.get::<_, Address>(&DataKey::MerchantBalance(old_address.clone()))
+    {
+        storage.remove(&DataKey::MerchantBalance(old_address.clone()));
+        storage.set(&DataKey::MerchantBalance(new_address.clone()), &account);
+    }
+
+    if let Some(account) = get_managed_account(env, merchant_id) {
+        MerchantAccountClient::new(env, &account).set_merchant(new_address);
+    }
+
+    events::publish_merchant_address_changed_event(
+        env,
+        merchant_id,
+        old_address,
+        new_address.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+/// Offboards a merchant. Pending invoices are voided, the merchant account
+/// is emptied back to the merchant and the address is released so it can
+/// register again under a new id.
+pub fn close_merchant(env: &Env, merchant: &Address) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    // Sellers would be left paying commission to a closed platform
+    if !platform::get_sub_merchants(env, merchant_id).is_empty() {
+        panic_with_error!(env, ContractError::PlatformHasSubMerchants);
+    }
+
+    // Without the index, pending invoices and the last payment of merchants
+    // registered before invoice tracking are unknown
+    if !invoice::is_invoice_index_complete(env, merchant_id) {
+        panic_with_error!(env, ContractError::InvoiceIndexIncomplete);
+    }
+
+    let now = env.ledger().timestamp();
+
+    // Funds must stay put until the most recent payment is past its refund
+    // window, even if that payment has since been refunded.
+    let refundable = stats::get_merchant_stats(env, merchant_id)
+        .last_payment_at
+        .is_some_and(|paid| now.saturating_sub(paid) <= invoice::MAX_REFUND_DURATION);
+    if refundable {
+        panic_with_error!(env, ContractError::MerchantHasOpenInvoices);
+    }
+
+    for invoice_id in invoice::get_pending_invoice_ids(env, merchant_id).iter() {
+        let mut pending_invoice = invoice::get_invoice(env, invoice_id);
+        pending_invoice.status = InvoiceStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &pending_invoice);
+        stats::record_invoice_cancelled(env, merchant_id);
+        events::publish_invoice_cancelled_event(env, invoice_id, merchant.clone(), now);
+    }
     env.storage()
         .persistent()
-        .set(&DataKey::MerchantKey(merchant.clone()), key);
+        .remove(&MerchantDataKey::PendingInvoices(merchant_id));
+
+    // Only accounts Shade manages are swept; Shade moves the funds as their
+    // manager, so closure does not depend on a nested merchant authorization.
+    // Legacy accounts outside the registry are left to the merchant.
+    if let Some(account) = get_managed_account(env, merchant_id) {
+        let account_client = MerchantAccountClient::new(env, &account);
+
+        // A deactivated merchant's account is restricted, which blocks the
+        // manager transfer; it is restricted again once swept.
+        if account_client.is_restricted_account() {
+            account_client.restrict_account(&false);
+        }
+
+        // Payments do not register tokens on the account, so sweep every
+        // accepted token as well as the ones the account tracks.
+        let mut tokens = admin::get_accepted_tokens(env);
+        for balance in account_client.get_balances().iter() {
+            if !tokens.contains(&balance.token) {
+                tokens.push_back(balance.token);
+            }
+        }
+        for token in tokens.iter() {
+            let balance = account_client.get_balance(&token);
+            if balance > 0 {
+                account_client.refund(&token, &balance, merchant);
+            }
+        }
+
+        account_client.restrict_account(&true);
+    }
+
+    let mut merchant_data = get_merchant(env, merchant_id);
+    merchant_data.active = false;
+
+    // Loaded before the key is removed so a legacy key without history is
+    // still recorded as revoked
+    let mut key_history = load_key_history(env, merchant_id, merchant);
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::Merchant(merchant_id), &merchant_data);
+    storage.set(&MerchantDataKey::Closed(merchant_id), &true);
+    storage.remove(&DataKey::MerchantId(merchant.clone()));
+    storage.remove(&DataKey::MerchantKey(merchant.clone()));
+    storage.remove(&DataKey::PendingMerchantAddress(merchant_id));
+
+    if revoke_active_key(&mut key_history, now).is_some() {
+        storage.set(&DataKey::MerchantKeyHistory(merchant_id), &key_history);
+    }
+
+    stake::start_cooldown(env, merchant_id);
+
+    events::publish_merchant_closed_event(env, merchant_id, merchant.clone(), now);
+}
+
+pub fn set_merchant_key(env: &Env, merchant: &Address, key: &BytesN<32>) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+    let now = env.ledger().timestamp();
+    let mut history = load_key_history(env, merchant_id, merchant);
+    let previous = revoke_active_key(&mut history, now);
+    let version = history.len() + 1;
+
+    history.push_back(MerchantKeyRecord {
+        key: key.clone(),
+        version,
+        activated_at: now,
+        revoked_at: None,
+    });
 
-    events::publish_merchant_key_set_event(
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::MerchantKeyHistory(merchant_id), &history);
+    storage.set(&DataKey::MerchantKey(merchant.clone()), key);
+
+    match previous {
+        Some(old) => events::publish_merchant_key_rotated_event(
+            env,
+            merchant.clone(),
+            old.key,
+            key.clone(),
+            version,
+            now,
+        ),
+        None => events::publish_merchant_key_set_event(env, merchant.clone(), key.clone(), now),
+    }
+}
+
+pub fn revoke_merchant_key(env: &Env, merchant: &Address) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+    let now = env.ledger().timestamp();
+    let mut history = load_key_history(env, merchant_id, merchant);
+    let revoked = revoke_active_key(&mut history, now)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantKeyNotFound));
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::MerchantKeyHistory(merchant_id), &history);
+    storage.remove(&DataKey::MerchantKey(merchant.clone()));
+
+    events::publish_merchant_key_revoked_event(
         env,
         merchant.clone(),
-        key.clone(),
-        env.ledger().timestamp(),
+        revoked.key,
+        revoked.version,
+        now,
     );
 }
 
@@ -181,6 +610,105 @@ pub fn get_merchant_key(env: &Env, merchant: &Address) -> BytesN<32> {
         .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantKeyNotFound))
 }
 
+pub fn get_merchant_keys(env: &Env, merchant_id: u64) -> Vec<MerchantKeyRecord> {
+    let merchant_data = get_merchant(env, merchant_id);
+    load_key_history(env, merchant_id, &merchant_data.address)
+}
+
+pub fn get_merchant_key_at(env: &Env, merchant_id: u64, timestamp: u64) -> BytesN<32> {
+    let mut found = None;
+    for record in get_merchant_keys(env, merchant_id).iter() {
+        let revoked = record.revoked_at.is_some_and(|at| at <= timestamp);
+        if record.activated_at <= timestamp && !revoked {
+            found = Some(record.key);
+        }
+    }
+
+    found.unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantKeyNotFound))
+}
+
+// Keys set before history tracking existed have no record; treat them as
+// active since genesis so rotating them still leaves an auditable entry.
+fn load_key_history(env: &Env, merchant_id: u64, merchant: &Address) -> Vec<MerchantKeyRecord> {
+    let storage = env.storage().persistent();
+    if let Some(history) = storage.get(&DataKey::MerchantKeyHistory(merchant_id)) {
+        return history;
+    }
+
+    let mut history = Vec::new(env);
+    if let Some(key) = storage.get::<_, BytesN<32>>(&DataKey::MerchantKey(merchant.clone())) {
+        history.push_back(MerchantKeyRecord {
+            key,
+            version: 1,
+            activated_at: 0,
+            revoked_at: None,
+        });
+    }
+    history
+}
+
+fn revoke_active_key(history: &mut Vec<MerchantKeyRecord>, now: u64) -> Option<MerchantKeyRecord> {
+    let index = history.len().checked_sub(1)?;
+    let mut record = history.get(index)?;
+    if record.revoked_at.is_some() {
+        return None;
+    }
+
+    record.revoked_at = Some(now);
+    history.set(index, record.clone());
+    Some(record)
+}
+
+fn is_valid_length(value: &String, min: u32, max: u32) -> bool {
+    value.len() >= min && value.len() <= max
+}
+
+pub fn set_merchant_profile(env: &Env, merchant: &Address, profile: MerchantProfile) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    save_merchant_profile(env, merchant_id, profile);
+}
+
+pub fn assert_valid_profile(env: &Env, profile: &MerchantProfile) {
+    // Website and logo are optional, name, category and country are not
+    if !is_valid_length(&profile.name, 1, MAX_PROFILE_NAME_LEN)
+        || !is_valid_length(&profile.website, 0, MAX_PROFILE_WEBSITE_LEN)
+        || !is_valid_length(&profile.logo_uri, 0, MAX_PROFILE_LOGO_URI_LEN)
+        || !is_valid_length(&profile.category, 1, MAX_PROFILE_CATEGORY_LEN)
+        || !is_valid_length(&profile.country_code, COUNTRY_CODE_LEN, COUNTRY_CODE_LEN)
+    {
+        panic_with_error!(env, ContractError::InvalidMerchantProfile);
+    }
+}
+
+pub fn save_merchant_profile(env: &Env, merchant_id: u64, profile: MerchantProfile) {
+    assert_valid_profile(env, &profile);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantProfile(merchant_id), &profile);
+
+    events::publish_merchant_profile_updated_event(
+        env,
+        merchant_id,
+        profile.category,
+        profile.country_code,
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn get_merchant_profile(env: &Env, merchant_id: u64) -> Option<MerchantProfile> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantProfile(merchant_id))
+}
+
 pub fn get_merchants(env: &Env, filter: MerchantFilter) -> Vec<Merchant> {
     let merchant_count: u64 = env
         .storage()
@@ -210,6 +738,57 @@ pub fn get_merchants(env: &Env, filter: MerchantFilter) -> Vec<Merchant> {
                 }
             }
 
+            if filter.category.is_some() || filter.country_code.is_some() {
+                match get_merchant_profile(env, merchant.id) {
+                    Some(profile) => {
+                        if let Some(category) = &filter.category {
+                            if profile.category != *category {
+                                matches = false;
+                            }
+                        }
+                        if let Some(country_code) = &filter.country_code {
+                            if profile.country_code != *country_code {
+                                matches = false;
+                            }
+                        }
+                    }
+                    None => matches = false,
+                }
+            }
+
+            // Date bounds are inclusive
+            if let Some(from) = filter.registered_from {
+                if merchant.date_registered < from {
+                    matches = false;
+                }
+            }
+
+            if let Some(until) = filter.registered_until {
+                if merchant.date_registered > until {
+                    matches = false;
+                }
+            }
+
+            if let Some(has_account) = filter.has_account {
+                let account_set = env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::MerchantAccount(merchant.id));
+                if account_set != has_account {
+                    matches = false;
+                }
+            }
+
+            if let Some(has_key) = filter.has_key {
+                let key_set = env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::MerchantKey(merchant.address.clone()));
+                if key_set != has_key {
+                    matches = false;
+                }
+            }
+
             if matches {
                 merchants.push_back(merchant);
             }
@@ -232,14 +811,327 @@ pub fn set_merchant_account(env: &Env, merchant: &Address, account: &Address) {
         .get(&DataKey::MerchantId(merchant.clone()))
         .unwrap();
 
+    // Only accounts deployed or approved by Shade for this merchant are accepted
+    let registered_for: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DeployedAccount(account.clone()));
+    if registered_for != Some(merchant_id) {
+        panic_with_error!(env, ContractError::InvalidMerchantAccount);
+    }
+
     env.storage()
         .persistent()
         .set(&DataKey::MerchantAccount(merchant_id), account);
 }
 
+/// Admin override for accounts that were not deployed through the factory,
+/// such as legacy accounts. The account must report the merchant and name
+/// Shade as its manager.
+pub fn approve_merchant_account(env: &Env, admin: &Address, merchant_id: u64, account: &Address) {
+    core::assert_admin(env, admin);
+
+    let merchant_data = get_merchant(env, merchant_id);
+
+    if !is_valid_merchant_account(env, &merchant_data, account) {
+        panic_with_error!(env, ContractError::InvalidMerchantAccount);
+    }
+
+    register_approved_account(env, &merchant_data, account);
+}
+
+fn is_valid_merchant_account(env: &Env, merchant_data: &Merchant, account: &Address) -> bool {
+    let account_client = MerchantAccountClient::new(env, account);
+    let reports_merchant = matches!(
+        account_client.try_get_merchant(),
+        Ok(Ok(account_merchant)) if account_merchant == merchant_data.address
+    );
+    let managed_by_shade = matches!(
+        account_client.try_get_manager(),
+        Ok(Ok(manager)) if manager == env.current_contract_address()
+    );
+    reports_merchant && managed_by_shade
+}
+
+fn register_approved_account(env: &Env, merchant_data: &Merchant, account: &Address) {
+    env.storage().persistent().set(
+        &DataKey::DeployedAccount(account.clone()),
+        &merchant_data.id,
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantAccount(merchant_data.id), account);
+    sync_account_status(env, merchant_data, account);
+
+    events::publish_merchant_account_approved_event(
+        env,
+        merchant_data.id,
+        account.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn deploy_merchant_account(env: &Env, merchant: &Address) -> Address {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::MerchantAccount(merchant_id))
+    {
+        panic_with_error!(env, ContractError::MerchantAccountAlreadySet);
+    }
+
+    let wasm_hash: BytesN<32> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccountWasmHash)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::WasmHashNotSet));
+
+    let account = account_factory::deploy_account(
+        env,
+        merchant.clone(),
+        env.current_contract_address(),
+        merchant_id,
+        wasm_hash,
+    );
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantAccount(merchant_id), &account);
+    sync_account_status(env, &get_merchant(env, merchant_id), &account);
+
+    account
+}
+
 pub fn get_merchant_account(env: &Env, merchant_id: u64) -> Address {
     env.storage()
         .persistent()
         .get(&DataKey::MerchantAccount(merchant_id))
         .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantAccountNotSet))
 }
+
+/// Returns the merchant's account if it is one Shade manages, i.e. it was
+/// deployed by the factory or approved by the admin for this merchant.
+pub fn get_managed_account(env: &Env, merchant_id: u64) -> Option<Address> {
+    let account: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantAccount(merchant_id))?;
+
+    let registered_for: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DeployedAccount(account.clone()));
+    if registered_for == Some(merchant_id) {
+        Some(account)
+    } else {
+        None
+    }
+}
+
+pub fn assert_managed_account(env: &Env, merchant_id: u64) -> Address {
+    // Also validates the merchant id
+    get_merchant(env, merchant_id);
+
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::MerchantAccount(merchant_id))
+    {
+        panic_with_error!(env, ContractError::MerchantAccountNotSet);
+    }
+
+    get_managed_account(env, merchant_id)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::InvalidMerchantAccount))
+}
+
+fn sync_account_status(env: &Env, merchant_data: &Merchant, account: &Address) {
+    let account_client = MerchantAccountClient::new(env, account);
+    if merchant_data.verified {
+        account_client.verify_account();
+    }
+    if !merchant_data.active {
+        account_client.restrict_account(&true);
+    }
+}
+
+pub fn add_merchant_account_token(env: &Env, caller: &Address, merchant_id: u64, token: &Address) {
+    access_control::assert_has_role(env, caller, Role::Operator);
+
+    let account = assert_managed_account(env, merchant_id);
+
+    if !admin::is_accepted_token(env, token) {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
+    }
+
+    MerchantAccountClient::new(env, &account).add_token(token);
+}
+
+pub fn verify_merchant_account(env: &Env, caller: &Address, merchant_id: u64) {
+    access_control::assert_has_role(env, caller, Role::Operator);
+
+    let account = assert_managed_account(env, merchant_id);
+    MerchantAccountClient::new(env, &account).verify_account();
+}
+
+pub fn restrict_merchant_account(env: &Env, caller: &Address, merchant_id: u64, status: bool) {
+    access_control::assert_has_role(env, caller, Role::Operator);
+
+    let account = assert_managed_account(env, merchant_id);
+    MerchantAccountClient::new(env, &account).restrict_account(&status);
+}
+
+pub fn set_fee_bearer(env: &Env, merchant: &Address, fee_bearer: FeeBearer) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantFeeBearer(merchant_id), &fee_bearer);
+
+    events::publish_fee_bearer_set_event(env, merchant_id, fee_bearer, env.ledger().timestamp());
+}
+
+pub fn get_fee_bearer(env: &Env, merchant_id: u64) -> FeeBearer {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantFeeBearer(merchant_id))
+        .unwrap_or(FeeBearer::Merchant)
+}
+
+/// Restricts the merchant to a subset of the globally accepted tokens. An
+/// empty list clears the allowlist so every accepted token is allowed again.
+pub fn set_merchant_tokens(env: &Env, merchant: &Address, tokens: Vec<Address>) {
+    merchant.require_auth();
+
+    let merchant_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantId(merchant.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::MerchantNotFound));
+
+    let mut allowlist: Vec<Address> = Vec::new(env);
+    for token in tokens.iter() {
+        if !admin::is_accepted_token(env, &token) {
+            panic_with_error!(env, ContractError::TokenNotAccepted);
+        }
+        if !allowlist.contains(&token) {
+            allowlist.push_back(token);
+        }
+    }
+
+    if allowlist.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MerchantTokenAllowlist(merchant_id));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantTokenAllowlist(merchant_id), &allowlist);
+    }
+
+    events::publish_merchant_tokens_set_event(
+        env,
+        merchant_id,
+        allowlist,
+        env.ledger().timestamp(),
+    );
+}
+
+/// Tokens the merchant can currently invoice in: the allowlist narrowed to
+/// tokens still globally accepted, or the global list when none is set.
+pub fn get_merchant_tokens(env: &Env, merchant_id: u64) -> Vec<Address> {
+    let accepted = admin::get_accepted_tokens(env);
+
+    match env
+        .storage()
+        .persistent()
+        .get::<_, Vec<Address>>(&DataKey::MerchantTokenAllowlist(merchant_id))
+    {
+        Some(allowlist) => {
+            let mut effective = Vec::new(env);
+            for token in allowlist.iter() {
+                if accepted.contains(&token) {
+                    effective.push_back(token);
+                }
+            }
+            effective
+        }
+        None => accepted,
+    }
+}
+
+pub fn is_merchant_token(env: &Env, merchant_id: u64, token: &Address) -> bool {
+    if !admin::is_accepted_token(env, token) {
+        return false;
+    }
+
+    match env
+        .storage()
+        .persistent()
+        .get::<_, Vec<Address>>(&DataKey::MerchantTokenAllowlist(merchant_id))
+    {
+        Some(allowlist) => allowlist.contains(token),
+        None => true,
+    }
+}
+
+/// Moves merchant accounts stored under the legacy `MerchantBalance(address)`
+/// key into the `MerchantAccount(id)` registry used for payments and refunds.
+/// Processes up to `limit` merchants starting at `start_id`, so large
+/// registries can be migrated over several calls. Legacy accounts go through
+/// the same checks as `approve_merchant_account`; ones that fail are left
+/// under the legacy key.
+pub fn migrate_merchant_accounts(env: &Env, admin: &Address, start_id: u64, limit: u32) -> u32 {
+    core::assert_admin(env, admin);
+
+    let merchant_count: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MerchantCount)
+        .unwrap_or(0);
+    let start_id = start_id.max(1);
+    let end_id = start_id
+        .saturating_add(limit as u64)
+        .saturating_sub(1)
+        .min(merchant_count);
+
+    let mut migrated: u32 = 0;
+
+    for merchant_id in start_id..=end_id {
+        if let Some(merchant) = env
+            .storage()
+            .persistent()
+            .get::<_, Merchant>(&DataKey::Merchant(merchant_id))
+        {
+            let legacy_key = DataKey::MerchantBalance(merchant.address.clone());
+            if let Some(account) = env.storage().persistent().get::<_, Address>(&legacy_key) {
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::MerchantAccount(merchant_id))
+                {
+                    env.storage().persistent().remove(&legacy_key);
+                } else if is_valid_merchant_account(env, &merchant, &account) {
+                    register_approved_account(env, &merchant, &account);
+                    env.storage().persistent().remove(&legacy_key);
+                    migrated += 1;
+                }
+            }
+        }
+    }
+
+    migrated
+}
@@ -1,9 +1,18 @@
 pub mod access_control;
 pub mod account_factory;
 pub mod admin;
+pub mod application;
 pub mod core;
 pub mod invoice;
+pub mod limits;
 pub mod merchant;
 pub mod pausable;
+pub mod platform;
+pub mod promotion;
 pub mod reentrancy;
+pub mod split;
+pub mod staff;
+pub mod stake;
+pub mod stats;
+pub mod terminal;
 pub mod upgrade;
@@ -0,0 +1,318 @@
+use crate::components::{access_control, admin, application, merchant, stats};
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{
+    DataKey, InvoiceCommission, InvoiceDataKey, MerchantDataKey, MerchantStats, PendingFee, Role,
+};
+use account::account::MerchantAccountClient;
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub const MAX_SUB_MERCHANTS: u32 = 500;
+
+/// Registers `sub_merchant` as a merchant owned by `platform`. While
+/// registration approval is required, only platforms approved by an operator
+/// may vouch for their sellers.
+pub fn register_sub_merchant(env: &Env, platform: &Address, sub_merchant: &Address) -> u64 {
+    platform.require_auth();
+    sub_merchant.require_auth();
+
+    let platform_id = merchant::get_merchant_id(env, platform);
+    merchant::assert_merchant_active(env, platform_id);
+
+    if application::is_approval_required(env) && !is_approved_platform(env, platform_id) {
+        panic_with_error!(env, ContractError::RegistrationRequiresApproval);
+    }
+
+    // Hierarchies are a single level deep
+    if get_parent_merchant(env, platform_id).is_some() {
+        panic_with_error!(env, ContractError::NestedSubMerchant);
+    }
+
+    let mut sub_merchants = get_sub_merchants(env, platform_id);
+    if sub_merchants.len() >= MAX_SUB_MERCHANTS {
+        panic_with_error!(env, ContractError::SubMerchantLimitExceeded);
+    }
+
+    let sub_merchant_id = merchant::create_merchant(env, sub_merchant);
+    sub_merchants.push_back(sub_merchant_id);
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::SubMerchants(platform_id), &sub_merchants);
+    storage.set(&DataKey::ParentMerchant(sub_merchant_id), &platform_id);
+
+    events::publish_sub_merchant_registered_event(
+        env,
+        platform_id,
+        sub_merchant_id,
+        sub_merchant.clone(),
+        env.ledger().timestamp(),
+    );
+
+    sub_merchant_id
+}
+
+/// Allows or stops a merchant registering sub-merchants while registration
+/// approval is required.
+pub fn set_platform_approval(env: &Env, reviewer: &Address, platform_id: u64, approved: bool) {
+    access_control::assert_has_role(env, reviewer, Role::Operator);
+    merchant::get_merchant(env, platform_id);
+
+    env.storage()
+        .persistent()
+        .set(&MerchantDataKey::ApprovedPlatform(platform_id), &approved);
+
+    events::publish_platform_approval_set_event(
+        env,
+        platform_id,
+        approved,
+        reviewer.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn is_approved_platform(env: &Env, platform_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&MerchantDataKey::ApprovedPlatform(platform_id))
+        .unwrap_or(false)
+}
+
+/// Detaches a sub-merchant from `platform`, after which it is an
+/// independent merchant that pays no commission. A platform can only be
+/// closed once it has no sub-merchants left.
+pub fn remove_sub_merchant(env: &Env, platform: &Address, sub_merchant_id: u64) {
+    platform.require_auth();
+
+    let platform_id = merchant::get_merchant_id(env, platform);
+    assert_sub_merchant_of(env, platform_id, sub_merchant_id);
+
+    let mut sub_merchants = get_sub_merchants(env, platform_id);
+    if let Some(index) = sub_merchants.first_index_of(sub_merchant_id) {
+        sub_merchants.remove(index);
+    }
+
+    let storage = env.storage().persistent();
+    storage.set(&DataKey::SubMerchants(platform_id), &sub_merchants);
+    storage.remove(&DataKey::ParentMerchant(sub_merchant_id));
+
+    events::publish_sub_merchant_removed_event(
+        env,
+        platform_id,
+        sub_merchant_id,
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn get_parent_merchant(env: &Env, merchant_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ParentMerchant(merchant_id))
+}
+
+pub fn get_sub_merchants(env: &Env, platform_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SubMerchants(platform_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn assert_sub_merchant_of(env: &Env, platform_id: u64, sub_merchant_id: u64) {
+    if get_parent_merchant(env, sub_merchant_id) != Some(platform_id) {
+        panic_with_error!(env, ContractError::NotAuthorized);
+    }
+}
+
+/// Commission in basis points taken from each payment to one of the
+/// platform's sub-merchants and sent to the platform's merchant account.
+/// Like protocol fees, increases only take effect after
+/// `admin::FEE_CHANGE_DELAY` so sub-merchants can react to them.
+pub fn set_platform_commission(env: &Env, platform: &Address, commission_bps: i128) {
+    platform.require_auth();
+
+    let platform_id = merchant::get_merchant_id(env, platform);
+
+    if !(0..=admin::BPS_DENOMINATOR).contains(&commission_bps) {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+
+    // Commission is paid into the platform's account and returned from it
+    // when a payment is refunded, so Shade must manage that account
+    if commission_bps > 0 {
+        merchant::assert_managed_account(env, platform_id);
+    }
+
+    activate_pending_commission(env, platform_id);
+
+    let now = env.ledger().timestamp();
+    let current_bps = get_platform_commission(env, platform_id);
+    let storage = env.storage().persistent();
+
+    if commission_bps <= current_bps {
+        // Lowering the commission applies immediately and supersedes any
+        // scheduled increase
+        storage.remove(&MerchantDataKey::PendingCommission(platform_id));
+        storage.set(&DataKey::PlatformCommission(platform_id), &commission_bps);
+
+        events::publish_platform_commission_set_event(env, platform_id, commission_bps, now);
+    } else {
+        let effective_at = now + admin::FEE_CHANGE_DELAY;
+        storage.set(
+            &MerchantDataKey::PendingCommission(platform_id),
+            &PendingFee {
+                fee: commission_bps,
+                effective_at,
+            },
+        );
+
+        events::publish_commission_scheduled_event(
+            env,
+            platform_id,
+            current_bps,
+            commission_bps,
+            effective_at,
+            now,
+        );
+    }
+}
+
+pub fn get_platform_commission(env: &Env, platform_id: u64) -> i128 {
+    if let Some(pending) = get_stored_pending_commission(env, platform_id) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            return pending.fee;
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformCommission(platform_id))
+        .unwrap_or(0)
+}
+
+/// Scheduled commission increase that has not taken effect yet.
+pub fn get_pending_platform_commission(env: &Env, platform_id: u64) -> Option<PendingFee> {
+    get_stored_pending_commission(env, platform_id)
+        .filter(|pending| env.ledger().timestamp() < pending.effective_at)
+}
+
+fn get_stored_pending_commission(env: &Env, platform_id: u64) -> Option<PendingFee> {
+    env.storage()
+        .persistent()
+        .get(&MerchantDataKey::PendingCommission(platform_id))
+}
+
+fn activate_pending_commission(env: &Env, platform_id: u64) {
+    if let Some(pending) = get_stored_pending_commission(env, platform_id) {
+        if env.ledger().timestamp() >= pending.effective_at {
+            let storage = env.storage().persistent();
+            storage.set(&DataKey::PlatformCommission(platform_id), &pending.fee);
+            storage.remove(&MerchantDataKey::PendingCommission(platform_id));
+        }
+    }
+}
+
+/// Returns the platform and commission owed on a payment of `amount` to
+/// `merchant_id`, or `None` when the merchant has no platform or the
+/// platform takes no commission. The commission never exceeds `cap`.
+pub fn calculate_commission(
+    env: &Env,
+    merchant_id: u64,
+    amount: i128,
+    cap: i128,
+) -> Option<(u64, i128)> {
+    let platform_id = get_parent_merchant(env, merchant_id)?;
+
+    // Deactivated and closed platforms stop earning commission
+    if !merchant::is_merchant_active(env, platform_id) {
+        return None;
+    }
+
+    let commission_bps = get_platform_commission(env, platform_id);
+    if commission_bps == 0 {
+        return None;
+    }
+
+    let commission = amount
+        .checked_mul(commission_bps)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+        / admin::BPS_DENOMINATOR;
+
+    Some((platform_id, commission.min(cap)))
+}
+
+pub fn record_commission(env: &Env, invoice_id: u64, platform_id: u64, amount: i128) {
+    env.storage().persistent().set(
+        &InvoiceDataKey::Commission(invoice_id),
+        &InvoiceCommission {
+            platform_id,
+            amount,
+        },
+    );
+}
+
+/// Returns the platform's part of a refund to the payer out of the
+/// platform's account and returns how much of the refund it covered. The
+/// commission is returned in proportion to the invoice amount refunded so
+/// far, so partial refunds never return more than was taken. A restricted
+/// platform account, such as that of a deactivated or closed platform,
+/// covers nothing and the sub-merchant refunds the commission instead.
+pub fn refund_commission(
+    env: &Env,
+    invoice_id: u64,
+    token: &Address,
+    payer: &Address,
+    invoice_amount: i128,
+    previously_refunded: i128,
+    total_refunded: i128,
+) -> i128 {
+    let Some(commission) = env
+        .storage()
+        .persistent()
+        .get::<_, InvoiceCommission>(&InvoiceDataKey::Commission(invoice_id))
+    else {
+        return 0;
+    };
+
+    let returned_by = |refunded: i128| {
+        commission
+            .amount
+            .checked_mul(refunded)
+            .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+            / invoice_amount
+    };
+    let share = returned_by(total_refunded) - returned_by(previously_refunded);
+    if share <= 0 {
+        return 0;
+    }
+
+    let Some(platform_account) = merchant::get_managed_account(env, commission.platform_id) else {
+        return 0;
+    };
+    let account_client = MerchantAccountClient::new(env, &platform_account);
+    if account_client.is_restricted_account() {
+        return 0;
+    }
+    account_client.refund(token, &share, payer);
+
+    events::publish_commission_refunded_event(
+        env,
+        invoice_id,
+        commission.platform_id,
+        share,
+        env.ledger().timestamp(),
+    );
+
+    share
+}
+
+/// Combined statistics of all of the platform's sub-merchants.
+pub fn get_platform_stats(env: &Env, platform_id: u64) -> MerchantStats {
+    merchant::get_merchant(env, platform_id);
+    let mut total = stats::empty_stats(env);
+
+    for sub_merchant_id in get_sub_merchants(env, platform_id).iter() {
+        let sub = stats::get_merchant_stats(env, sub_merchant_id);
+        stats::add_stats(env, &mut total, &sub);
+    }
+
+    total
+}
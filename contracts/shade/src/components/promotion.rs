@@ -0,0 +1,197 @@
+use crate::components::{admin, core, merchant};
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{DataKey, Promotion, PromotionAllowance, PromotionRecord};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+pub fn set_promotion(
+    env: &Env,
+    admin: &Address,
+    token: &Address,
+    merchant_id: Option<u64>,
+    promotion: &Promotion,
+) {
+    core::assert_admin(env, admin);
+
+    if !admin::is_accepted_token(env, token) {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
+    }
+
+    if let Some(merchant_id) = merchant_id {
+        merchant::get_merchant(env, merchant_id);
+    }
+
+    // A promotion must be bounded by time, volume or both
+    if promotion.max_age.is_none() && promotion.volume_cap.is_none() {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+    if promotion.max_age == Some(0) {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+    if let Some(volume_cap) = promotion.volume_cap {
+        if volume_cap <= 0 {
+            panic_with_error!(env, ContractError::InvalidAmount);
+        }
+    }
+
+    // Every promotion gets a fresh id, so usage of a replaced promotion is
+    // never carried over to its successor.
+    let key = promotion_key(token, merchant_id);
+    clear_usage(env, &key, merchant_id);
+
+    let id = env
+        .storage()
+        .persistent()
+        .get::<_, u64>(&DataKey::PromotionCount)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::PromotionCount, &id);
+    env.storage().persistent().set(
+        &key,
+        &PromotionRecord {
+            id,
+            promotion: promotion.clone(),
+        },
+    );
+
+    events::publish_promotion_set_event(
+        env,
+        token.clone(),
+        merchant_id,
+        promotion.max_age,
+        promotion.volume_cap,
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn remove_promotion(env: &Env, admin: &Address, token: &Address, merchant_id: Option<u64>) {
+    core::assert_admin(env, admin);
+
+    let key = promotion_key(token, merchant_id);
+    if !env.storage().persistent().has(&key) {
+        panic_with_error!(env, ContractError::PromotionNotFound);
+    }
+
+    clear_usage(env, &key, merchant_id);
+    env.storage().persistent().remove(&key);
+
+    events::publish_promotion_removed_event(
+        env,
+        token.clone(),
+        merchant_id,
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn get_promotion_allowance(
+    env: &Env,
+    merchant_id: u64,
+    token: &Address,
+) -> Option<PromotionAllowance> {
+    get_active_allowance(env, merchant_id, token).map(|(_, allowance)| allowance)
+}
+
+/// Returns the id of the promotion in effect along with what remains of it.
+fn get_active_allowance(
+    env: &Env,
+    merchant_id: u64,
+    token: &Address,
+) -> Option<(u64, PromotionAllowance)> {
+    let merchant_data = merchant::get_merchant(env, merchant_id);
+
+    let record = get_active_promotion(env, merchant_id, token)?;
+    let promotion = record.promotion;
+
+    let time_remaining = match promotion.max_age {
+        Some(max_age) => {
+            let ends_at = merchant_data.date_registered.saturating_add(max_age);
+            let remaining = ends_at.saturating_sub(env.ledger().timestamp());
+            if remaining == 0 {
+                return None;
+            }
+            Some(remaining)
+        }
+        None => None,
+    };
+
+    let volume_remaining = match promotion.volume_cap {
+        Some(volume_cap) => {
+            let remaining = volume_cap - get_promotion_usage(env, record.id, merchant_id);
+            if remaining <= 0 {
+                return None;
+            }
+            Some(remaining)
+        }
+        None => None,
+    };
+
+    Some((
+        record.id,
+        PromotionAllowance {
+            time_remaining,
+            volume_remaining,
+        },
+    ))
+}
+
+/// Consumes promotional allowance for a payment and returns the portion of
+/// `amount` that is exempt from fees.
+pub fn apply_promotion(env: &Env, merchant_id: u64, token: &Address, amount: i128) -> i128 {
+    let (promotion_id, allowance) = match get_active_allowance(env, merchant_id, token) {
+        Some(active) => active,
+        None => return 0,
+    };
+
+    let waived_amount = match allowance.volume_remaining {
+        Some(volume_remaining) if volume_remaining < amount => volume_remaining,
+        _ => amount,
+    };
+
+    let usage = get_promotion_usage(env, promotion_id, merchant_id)
+        .checked_add(waived_amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    env.storage()
+        .persistent()
+        .set(&DataKey::PromotionUsage(promotion_id, merchant_id), &usage);
+
+    waived_amount
+}
+
+/// A merchant-specific promotion takes precedence over the global one.
+fn get_active_promotion(env: &Env, merchant_id: u64, token: &Address) -> Option<PromotionRecord> {
+    env.storage()
+        .persistent()
+        .get(&promotion_key(token, Some(merchant_id)))
+        .or_else(|| env.storage().persistent().get(&promotion_key(token, None)))
+}
+
+fn get_promotion_usage(env: &Env, promotion_id: u64, merchant_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PromotionUsage(promotion_id, merchant_id))
+        .unwrap_or(0)
+}
+
+/// Drops the usage of a merchant promotion that is being replaced or removed.
+/// Usage of a global promotion is spread over every merchant and is left to
+/// go stale under its retired id.
+fn clear_usage(env: &Env, key: &DataKey, merchant_id: Option<u64>) {
+    let merchant_id = match merchant_id {
+        Some(merchant_id) => merchant_id,
+        None => return,
+    };
+    if let Some(record) = env.storage().persistent().get::<_, PromotionRecord>(key) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PromotionUsage(record.id, merchant_id));
+    }
+}
+
+fn promotion_key(token: &Address, merchant_id: Option<u64>) -> DataKey {
+    match merchant_id {
+        Some(merchant_id) => DataKey::MerchantPromotion(merchant_id, token.clone()),
+        None => DataKey::GlobalPromotion(token.clone()),
+    }
+}
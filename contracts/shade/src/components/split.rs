@@ -0,0 +1,195 @@
+use crate::components::{admin, invoice};
+use crate::errors::{ContractError, SettlementError};
+use crate::events;
+use crate::types::{DataKey, Invoice, InvoiceDataKey, InvoiceStatus, SplitPayout, SplitRecipient};
+use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
+
+pub const MAX_SPLIT_RECIPIENTS: u32 = 10;
+
+/// Recipients must be distinct with positive shares adding up to at most
+/// 100%; whatever is not allocated stays with the merchant.
+pub fn assert_valid_split(env: &Env, split: &Vec<SplitRecipient>) {
+    if split.is_empty() || split.len() > MAX_SPLIT_RECIPIENTS {
+        panic_with_error!(env, ContractError::InvalidSplit);
+    }
+
+    let mut total_bps: i128 = 0;
+    let mut recipients: Vec<Address> = Vec::new(env);
+    for entry in split.iter() {
+        if entry.share_bps <= 0 || recipients.contains(&entry.recipient) {
+            panic_with_error!(env, ContractError::InvalidSplit);
+        }
+        total_bps += entry.share_bps;
+        recipients.push_back(entry.recipient);
+    }
+
+    if total_bps > admin::BPS_DENOMINATOR {
+        panic_with_error!(env, ContractError::InvalidSplit);
+    }
+}
+
+pub fn set_invoice_split(env: &Env, invoice_id: u64, split: &Vec<SplitRecipient>) {
+    assert_valid_split(env, split);
+    env.storage()
+        .persistent()
+        .set(&DataKey::InvoiceSplit(invoice_id), split);
+}
+
+pub fn get_invoice_split(env: &Env, invoice_id: u64) -> Vec<SplitRecipient> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InvoiceSplit(invoice_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn share_of(env: &Env, amount: i128, share_bps: i128) -> i128 {
+    amount
+        .checked_mul(share_bps)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+        / admin::BPS_DENOMINATOR
+}
+
+/// Moves each recipient's share of `amount` from the payer into escrow and
+/// returns what is left for the merchant, including rounding remainders.
+/// Shares stay in escrow until the refund window closes, so refunds can be
+/// reversed from them without any recipient's authorization.
+pub fn pay_split(
+    env: &Env,
+    invoice_id: u64,
+    token: &Address,
+    payer: &Address,
+    amount: i128,
+) -> i128 {
+    let split = get_invoice_split(env, invoice_id);
+    if split.is_empty() {
+        return amount;
+    }
+
+    let token_client = token::TokenClient::new(env, token);
+    let escrow = env.current_contract_address();
+    let mut remaining = amount;
+    let mut payouts: Vec<SplitPayout> = Vec::new(env);
+
+    for entry in split.iter() {
+        let share = share_of(env, amount, entry.share_bps);
+        if share > 0 {
+            token_client.transfer(payer, &escrow, &share);
+            remaining -= share;
+            payouts.push_back(SplitPayout {
+                recipient: entry.recipient.clone(),
+                amount: share,
+                refunded: 0,
+            });
+            events::publish_split_paid_event(
+                env,
+                invoice_id,
+                entry.recipient,
+                share,
+                env.ledger().timestamp(),
+            );
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&InvoiceDataKey::SplitPayouts(invoice_id), &payouts);
+
+    remaining
+}
+
+pub fn get_split_payouts(env: &Env, invoice_id: u64) -> Vec<SplitPayout> {
+    env.storage()
+        .persistent()
+        .get(&InvoiceDataKey::SplitPayouts(invoice_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Returns each recipient's part of a refund that brings the invoice's
+/// refunded total to `total_refunded` from escrow to the payer, and returns
+/// how much of the refund the recipients covered. Parts are proportional to
+/// what each recipient was paid, so over all refunds they never exceed it.
+pub fn refund_split(env: &Env, invoice: &Invoice, payer: &Address, total_refunded: i128) -> i128 {
+    let mut payouts = get_split_payouts(env, invoice.id);
+    if payouts.is_empty() {
+        return 0;
+    }
+
+    let token_client = token::TokenClient::new(env, &invoice.token);
+    let escrow = env.current_contract_address();
+    let mut covered: i128 = 0;
+
+    for (index, mut payout) in payouts.clone().iter().enumerate() {
+        let refunded_by = |refunded: i128| {
+            payout
+                .amount
+                .checked_mul(refunded)
+                .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+                / invoice.amount
+        };
+        let share = refunded_by(total_refunded) - refunded_by(invoice.amount_refunded);
+        if share <= 0 {
+            continue;
+        }
+
+        token_client.transfer(&escrow, payer, &share);
+        payout.refunded += share;
+        covered += share;
+        payouts.set(index as u32, payout.clone());
+
+        events::publish_split_refunded_event(
+            env,
+            invoice.id,
+            payout.recipient,
+            share,
+            env.ledger().timestamp(),
+        );
+    }
+
+    env.storage()
+        .persistent()
+        .set(&InvoiceDataKey::SplitPayouts(invoice.id), &payouts);
+
+    covered
+}
+
+/// Pays out what is left of each recipient's share once the invoice can no
+/// longer be refunded. Anyone may trigger the release.
+pub fn release_split_payouts(env: &Env, invoice_id: u64) {
+    let invoice = invoice::get_invoice(env, invoice_id);
+
+    let date_paid = match invoice.status {
+        InvoiceStatus::Paid | InvoiceStatus::PartiallyRefunded | InvoiceStatus::Refunded => {
+            invoice.date_paid
+        }
+        _ => None,
+    }
+    .unwrap_or_else(|| panic_with_error!(env, ContractError::InvalidInvoiceStatus));
+
+    let now = env.ledger().timestamp();
+    if now.saturating_sub(date_paid) <= invoice::MAX_REFUND_DURATION {
+        panic_with_error!(env, SettlementError::SplitEscrowLocked);
+    }
+
+    let released_key = InvoiceDataKey::SplitReleased(invoice_id);
+    if env.storage().persistent().has(&released_key) {
+        panic_with_error!(env, SettlementError::SplitAlreadyReleased);
+    }
+    env.storage().persistent().set(&released_key, &true);
+
+    let token_client = token::TokenClient::new(env, &invoice.token);
+    let escrow = env.current_contract_address();
+
+    for payout in get_split_payouts(env, invoice_id).iter() {
+        let remaining = payout.amount - payout.refunded;
+        if remaining > 0 {
+            token_client.transfer(&escrow, &payout.recipient, &remaining);
+            events::publish_split_released_event(env, invoice_id, payout.recipient, remaining, now);
+        }
+    }
+}
+
+pub fn is_split_released(env: &Env, invoice_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&InvoiceDataKey::SplitReleased(invoice_id))
+}
@@ -0,0 +1,151 @@
+use crate::components::merchant;
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{DataKey, InvoiceDataKey, MerchantRole, StaffMember};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+/// Grants `role` to `staff`. A staff address works for a single merchant at
+/// a time. `refund_limit` caps the total a refund manager may refund on any
+/// one invoice and is only valid for refund managers.
+pub fn grant_merchant_role(
+    env: &Env,
+    merchant: &Address,
+    staff: &Address,
+    role: MerchantRole,
+    refund_limit: Option<i128>,
+) {
+    merchant.require_auth();
+    let merchant_id = merchant::get_merchant_id(env, merchant);
+
+    if merchant::is_merchant(env, staff) {
+        panic_with_error!(env, ContractError::NotAuthorized);
+    }
+
+    match (role, refund_limit) {
+        (MerchantRole::RefundManager, Some(limit)) if limit > 0 => {}
+        (MerchantRole::Cashier | MerchantRole::Accountant, None) => {}
+        _ => panic_with_error!(env, ContractError::InvalidAmount),
+    }
+
+    let key = DataKey::Staff(staff.clone());
+    let mut member = get_staff_member(env, staff).unwrap_or(StaffMember {
+        merchant_id,
+        roles: Vec::new(env),
+        refund_limit: None,
+    });
+    if member.merchant_id != merchant_id {
+        panic_with_error!(env, ContractError::StaffBelongsToOtherMerchant);
+    }
+
+    if !member.roles.contains(role) {
+        member.roles.push_back(role);
+    }
+    if role == MerchantRole::RefundManager {
+        member.refund_limit = refund_limit;
+    }
+    env.storage().persistent().set(&key, &member);
+
+    events::publish_staff_role_granted_event(
+        env,
+        merchant_id,
+        staff.clone(),
+        role,
+        refund_limit,
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn revoke_merchant_role(env: &Env, merchant: &Address, staff: &Address, role: MerchantRole) {
+    merchant.require_auth();
+    let merchant_id = merchant::get_merchant_id(env, merchant);
+
+    let key = DataKey::Staff(staff.clone());
+    let mut member: StaffMember = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .filter(|member: &StaffMember| member.merchant_id == merchant_id)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::NotAuthorized));
+
+    if let Some(index) = member.roles.first_index_of(role) {
+        member.roles.remove(index);
+    }
+    if role == MerchantRole::RefundManager {
+        member.refund_limit = None;
+    }
+
+    // Staff without any role are released so another merchant can hire them
+    if member.roles.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &member);
+    }
+
+    events::publish_staff_role_revoked_event(
+        env,
+        merchant_id,
+        staff.clone(),
+        role,
+        env.ledger().timestamp(),
+    );
+}
+
+/// Staff of a closed merchant are released, so their leftover record is
+/// ignored and another merchant may hire them.
+pub fn get_staff_member(env: &Env, staff: &Address) -> Option<StaffMember> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Staff(staff.clone()))
+        .filter(|member: &StaffMember| !merchant::is_merchant_closed(env, member.merchant_id))
+}
+
+pub fn has_merchant_role(env: &Env, staff: &Address, role: MerchantRole) -> bool {
+    get_staff_member(env, staff).is_some_and(|member| member.roles.contains(role))
+}
+
+/// Counts a refund by `staff` towards their limit for the invoice, so the
+/// limit cannot be sidestepped by splitting a refund into smaller calls.
+pub fn record_refund(
+    env: &Env,
+    staff: &Address,
+    invoice_id: u64,
+    amount: i128,
+    refund_limit: i128,
+) {
+    let key = InvoiceDataKey::StaffRefunded(invoice_id, staff.clone());
+    let refunded: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    let refunded = refunded
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    if refunded > refund_limit {
+        panic_with_error!(env, ContractError::StaffLimitExceeded);
+    }
+    env.storage().persistent().set(&key, &refunded);
+}
+
+/// Resolves the merchant `caller` acts for, either as the merchant itself
+/// or as staff holding `role`. Returns the merchant id and, for staff, the
+/// refund limit that applies to them.
+pub fn authorize(env: &Env, caller: &Address, role: MerchantRole) -> (u64, Option<i128>) {
+    caller.require_auth();
+
+    if let Some(merchant_id) = env
+        .storage()
+        .persistent()
+        .get::<_, u64>(&DataKey::MerchantId(caller.clone()))
+    {
+        return (merchant_id, None);
+    }
+
+    match get_staff_member(env, caller) {
+        Some(member) if member.roles.contains(role) => {
+            let limit = if role == MerchantRole::RefundManager {
+                member.refund_limit
+            } else {
+                None
+            };
+            (member.merchant_id, limit)
+        }
+        _ => panic_with_error!(env, ContractError::NotAuthorized),
+    }
+}
@@ -0,0 +1,199 @@
+use crate::components::{access_control, admin, core, merchant};
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::{DataKey, MerchantStake, Role, StakeRequirement};
+use soroban_sdk::{panic_with_error, token, Address, Env};
+
+/// Time a closed merchant's stake stays slashable before it can be withdrawn.
+pub const STAKE_COOLDOWN: u64 = 30 * 86_400;
+
+/// Sets the collateral merchants must lock before they can be verified;
+/// `None` removes the requirement. Verification rather than registration is
+/// the gate because a stake is held against a merchant id, which only exists
+/// once the merchant is registered. Unverified merchants stay within the
+/// limits of the unverified tier.
+pub fn set_stake_requirement(env: &Env, admin: &Address, requirement: &Option<StakeRequirement>) {
+    core::assert_admin(env, admin);
+
+    match requirement {
+        Some(requirement) => {
+            if !admin::is_accepted_token(env, &requirement.token) {
+                panic_with_error!(env, ContractError::TokenNotAccepted);
+            }
+            if requirement.amount <= 0 {
+                panic_with_error!(env, ContractError::InvalidAmount);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::StakeRequirement, requirement);
+        }
+        None => env
+            .storage()
+            .persistent()
+            .remove(&DataKey::StakeRequirement),
+    }
+
+    events::publish_stake_requirement_set_event(env, requirement.clone(), env.ledger().timestamp());
+}
+
+pub fn get_stake_requirement(env: &Env) -> Option<StakeRequirement> {
+    env.storage().persistent().get(&DataKey::StakeRequirement)
+}
+
+pub fn get_merchant_stake(env: &Env, merchant_id: u64) -> Option<MerchantStake> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantStake(merchant_id))
+}
+
+pub fn has_required_stake(env: &Env, merchant_id: u64) -> bool {
+    let Some(requirement) = get_stake_requirement(env) else {
+        return true;
+    };
+
+    get_merchant_stake(env, merchant_id)
+        .is_some_and(|stake| stake.token == requirement.token && stake.amount >= requirement.amount)
+}
+
+pub fn deposit_stake(env: &Env, merchant: &Address, amount: i128) {
+    merchant.require_auth();
+
+    let merchant_id = merchant::get_merchant_id(env, merchant);
+    let requirement = get_stake_requirement(env)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::StakeNotConfigured));
+
+    if amount <= 0 {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+
+    let mut stake = get_merchant_stake(env, merchant_id).unwrap_or(MerchantStake {
+        token: requirement.token.clone(),
+        amount: 0,
+        slashed: 0,
+        unlock_at: None,
+    });
+
+    // A leftover stake in a previously required token must be topped up in
+    // that token; it can only switch once nothing is locked.
+    if stake.amount == 0 {
+        stake.token = requirement.token;
+    } else if stake.token != requirement.token {
+        panic_with_error!(env, ContractError::TokenNotAccepted);
+    }
+
+    token::TokenClient::new(env, &stake.token).transfer(
+        merchant,
+        env.current_contract_address(),
+        &amount,
+    );
+
+    stake.amount = stake
+        .amount
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    save_stake(env, merchant_id, &stake);
+
+    events::publish_stake_deposited_event(
+        env,
+        merchant_id,
+        stake.token,
+        amount,
+        env.ledger().timestamp(),
+    );
+}
+
+/// Moves part of a merchant's stake to `recipient`, typically the payer who
+/// won a dispute. Closed merchants remain slashable during the cooldown.
+pub fn slash_stake(
+    env: &Env,
+    caller: &Address,
+    merchant_id: u64,
+    amount: i128,
+    recipient: &Address,
+) {
+    access_control::assert_has_role(env, caller, Role::Operator);
+
+    if amount <= 0 {
+        panic_with_error!(env, ContractError::InvalidAmount);
+    }
+
+    let mut stake = get_merchant_stake(env, merchant_id)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::InsufficientStake));
+    if amount > stake.amount {
+        panic_with_error!(env, ContractError::InsufficientStake);
+    }
+
+    stake.amount -= amount;
+    stake.slashed = stake
+        .slashed
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow));
+    save_stake(env, merchant_id, &stake);
+
+    token::TokenClient::new(env, &stake.token).transfer(
+        &env.current_contract_address(),
+        recipient,
+        &amount,
+    );
+
+    events::publish_stake_slashed_event(
+        env,
+        merchant_id,
+        stake.token,
+        amount,
+        recipient.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn withdraw_stake(env: &Env, merchant_id: u64) {
+    let merchant_data = merchant::get_merchant(env, merchant_id);
+    merchant_data.address.require_auth();
+
+    let mut stake = get_merchant_stake(env, merchant_id)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::InsufficientStake));
+
+    let unlocked = merchant::is_merchant_closed(env, merchant_id)
+        && stake
+            .unlock_at
+            .is_some_and(|unlock_at| env.ledger().timestamp() >= unlock_at);
+    if !unlocked {
+        panic_with_error!(env, ContractError::StakeLocked);
+    }
+
+    let amount = stake.amount;
+    if amount == 0 {
+        panic_with_error!(env, ContractError::InsufficientStake);
+    }
+
+    stake.amount = 0;
+    save_stake(env, merchant_id, &stake);
+
+    token::TokenClient::new(env, &stake.token).transfer(
+        &env.current_contract_address(),
+        &merchant_data.address,
+        &amount,
+    );
+
+    events::publish_stake_withdrawn_event(
+        env,
+        merchant_id,
+        stake.token,
+        amount,
+        env.ledger().timestamp(),
+    );
+}
+
+/// Starts the withdrawal cooldown when the merchant closes.
+pub fn start_cooldown(env: &Env, merchant_id: u64) {
+    if let Some(mut stake) = get_merchant_stake(env, merchant_id) {
+        stake.unlock_at = Some(env.ledger().timestamp().saturating_add(STAKE_COOLDOWN));
+        save_stake(env, merchant_id, &stake);
+    }
+}
+
+fn save_stake(env: &Env, merchant_id: u64, stake: &MerchantStake) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantStake(merchant_id), stake);
+}
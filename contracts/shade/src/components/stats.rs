@@ -0,0 +1,189 @@
+use crate::components::merchant;
+use crate::errors::ContractError;
+use crate::types::{DataKey, InvoiceStatus, MerchantStats, TokenVolume};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub fn get_merchant_stats(env: &Env, merchant_id: u64) -> MerchantStats {
+    // Panics with MerchantNotFound for unknown ids
+    merchant::get_merchant(env, merchant_id);
+
+    env.storage()
+        .persistent()
+        .get(&DataKey::MerchantStats(merchant_id))
+        .unwrap_or_else(|| empty_stats(env))
+}
+
+pub fn empty_stats(env: &Env) -> MerchantStats {
+    MerchantStats {
+        invoice_count: 0,
+        pending_count: 0,
+        paid_count: 0,
+        cancelled_count: 0,
+        refunded_count: 0,
+        partially_refunded_count: 0,
+        first_payment_at: None,
+        last_payment_at: None,
+        volumes: Vec::new(env),
+    }
+}
+
+pub fn record_invoice_created(env: &Env, merchant_id: u64) {
+    let mut stats = get_merchant_stats(env, merchant_id);
+    stats.invoice_count = add_count(env, stats.invoice_count, 1);
+    stats.pending_count = add_count(env, stats.pending_count, 1);
+    save(env, merchant_id, &stats);
+}
+
+pub fn record_invoice_paid(env: &Env, merchant_id: u64, token: &Address, amount: i128, fee: i128) {
+    let now = env.ledger().timestamp();
+    let mut stats = get_merchant_stats(env, merchant_id);
+    move_status(env, &mut stats, InvoiceStatus::Pending, InvoiceStatus::Paid);
+
+    if stats.first_payment_at.is_none() {
+        stats.first_payment_at = Some(now);
+    }
+    stats.last_payment_at = Some(now);
+
+    let mut volume = token_volume(&stats, token);
+    volume.gross_volume = add_amount(env, volume.gross_volume, amount);
+    volume.fees_paid = add_amount(env, volume.fees_paid, fee);
+    set_token_volume(&mut stats, volume);
+
+    save(env, merchant_id, &stats);
+}
+
+pub fn record_invoice_refunded(
+    env: &Env,
+    merchant_id: u64,
+    token: &Address,
+    amount: i128,
+    from: InvoiceStatus,
+    to: InvoiceStatus,
+) {
+    let mut stats = get_merchant_stats(env, merchant_id);
+    move_status(env, &mut stats, from, to);
+
+    let mut volume = token_volume(&stats, token);
+    volume.refunded = add_amount(env, volume.refunded, amount);
+    set_token_volume(&mut stats, volume);
+
+    save(env, merchant_id, &stats);
+}
+
+pub fn record_invoice_cancelled(env: &Env, merchant_id: u64) {
+    let mut stats = get_merchant_stats(env, merchant_id);
+    move_status(
+        env,
+        &mut stats,
+        InvoiceStatus::Pending,
+        InvoiceStatus::Cancelled,
+    );
+    save(env, merchant_id, &stats);
+}
+
+/// Records a payment made before stats were tracked, found while migrating
+/// a legacy merchant's invoices.
+pub fn record_legacy_payment(env: &Env, merchant_id: u64, paid_at: u64) {
+    let mut stats = get_merchant_stats(env, merchant_id);
+    if stats.first_payment_at.is_none_or(|first| paid_at < first) {
+        stats.first_payment_at = Some(paid_at);
+    }
+    if stats.last_payment_at.is_none_or(|last| paid_at > last) {
+        stats.last_payment_at = Some(paid_at);
+    }
+    save(env, merchant_id, &stats);
+}
+
+// Invoices created before stats were tracked were never counted, so the
+// source bucket saturates at zero instead of underflowing.
+fn move_status(env: &Env, stats: &mut MerchantStats, from: InvoiceStatus, to: InvoiceStatus) {
+    if from == to {
+        return;
+    }
+
+    let source = status_count(stats, from);
+    *source = source.saturating_sub(1);
+    let target = status_count(stats, to);
+    *target = add_count(env, *target, 1);
+}
+
+fn status_count(stats: &mut MerchantStats, status: InvoiceStatus) -> &mut u64 {
+    match status {
+        InvoiceStatus::Pending => &mut stats.pending_count,
+        InvoiceStatus::Paid => &mut stats.paid_count,
+        InvoiceStatus::Cancelled => &mut stats.cancelled_count,
+        InvoiceStatus::Refunded => &mut stats.refunded_count,
+        InvoiceStatus::PartiallyRefunded => &mut stats.partially_refunded_count,
+    }
+}
+
+fn token_volume(stats: &MerchantStats, token: &Address) -> TokenVolume {
+    for volume in stats.volumes.iter() {
+        if volume.token == *token {
+            return volume;
+        }
+    }
+
+    TokenVolume {
+        token: token.clone(),
+        gross_volume: 0,
+        fees_paid: 0,
+        refunded: 0,
+    }
+}
+
+/// Folds the statistics of another merchant into `total`.
+pub fn add_stats(env: &Env, total: &mut MerchantStats, other: &MerchantStats) {
+    total.invoice_count = add_count(env, total.invoice_count, other.invoice_count);
+    total.pending_count = add_count(env, total.pending_count, other.pending_count);
+    total.paid_count = add_count(env, total.paid_count, other.paid_count);
+    total.cancelled_count = add_count(env, total.cancelled_count, other.cancelled_count);
+    total.refunded_count = add_count(env, total.refunded_count, other.refunded_count);
+    total.partially_refunded_count = add_count(
+        env,
+        total.partially_refunded_count,
+        other.partially_refunded_count,
+    );
+
+    total.first_payment_at = match (total.first_payment_at, other.first_payment_at) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    total.last_payment_at = total.last_payment_at.max(other.last_payment_at);
+
+    for volume in other.volumes.iter() {
+        let mut sum = token_volume(total, &volume.token);
+        sum.gross_volume = add_amount(env, sum.gross_volume, volume.gross_volume);
+        sum.fees_paid = add_amount(env, sum.fees_paid, volume.fees_paid);
+        sum.refunded = add_amount(env, sum.refunded, volume.refunded);
+        set_token_volume(total, sum);
+    }
+}
+
+fn add_count(env: &Env, count: u64, increment: u64) -> u64 {
+    count
+        .checked_add(increment)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+}
+
+fn add_amount(env: &Env, amount: i128, increment: i128) -> i128 {
+    amount
+        .checked_add(increment)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::ArithmeticOverflow))
+}
+
+fn set_token_volume(stats: &mut MerchantStats, volume: TokenVolume) {
+    for (index, existing) in stats.volumes.iter().enumerate() {
+        if existing.token == volume.token {
+            stats.volumes.set(index as u32, volume);
+            return;
+        }
+    }
+    stats.volumes.push_back(volume);
+}
+
+fn save(env: &Env, merchant_id: u64, stats: &MerchantStats) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MerchantStats(merchant_id), stats);
+}
@@ -0,0 +1,81 @@
+use crate::components::merchant;
+use crate::errors::ContractError;
+use crate::events;
+use crate::types::DataKey;
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, Vec};
+
+pub const MAX_TERMINAL_KEYS: u32 = 20;
+
+pub fn get_terminal_keys(env: &Env, merchant_id: u64) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TerminalKeys(merchant_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn is_terminal_key(env: &Env, merchant_id: u64, key: &BytesN<32>) -> bool {
+    get_terminal_keys(env, merchant_id).contains(key)
+}
+
+pub fn add_terminal_key(env: &Env, merchant: &Address, key: &BytesN<32>) {
+    merchant.require_auth();
+    let merchant_id = merchant::get_merchant_id(env, merchant);
+
+    let mut keys = get_terminal_keys(env, merchant_id);
+    if keys.contains(key) {
+        return;
+    }
+    if keys.len() >= MAX_TERMINAL_KEYS {
+        panic_with_error!(env, ContractError::TooManyTerminalKeys);
+    }
+
+    keys.push_back(key.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::TerminalKeys(merchant_id), &keys);
+
+    events::publish_terminal_key_added_event(
+        env,
+        merchant_id,
+        key.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn remove_terminal_key(env: &Env, merchant: &Address, key: &BytesN<32>) {
+    merchant.require_auth();
+    let merchant_id = merchant::get_merchant_id(env, merchant);
+
+    let mut keys = get_terminal_keys(env, merchant_id);
+    let index = keys
+        .first_index_of(key)
+        .unwrap_or_else(|| panic_with_error!(env, ContractError::TerminalKeyNotFound));
+
+    keys.remove(index);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TerminalKeys(merchant_id), &keys);
+
+    events::publish_terminal_key_removed_event(
+        env,
+        merchant_id,
+        key.clone(),
+        env.ledger().timestamp(),
+    );
+}
+
+pub fn is_nonce_used(env: &Env, key: &BytesN<32>, nonce: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::TerminalNonce(key.clone(), nonce))
+}
+
+pub fn use_nonce(env: &Env, key: &BytesN<32>, nonce: u64) {
+    if is_nonce_used(env, key, nonce) {
+        panic_with_error!(env, ContractError::NonceAlreadyUsed);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::TerminalNonce(key.clone(), nonce), &true);
+}
@@ -22,4 +22,46 @@ pub enum ContractError {
     WasmHashNotSet = 16,
     InvoiceAlreadyPaid = 17,
     MerchantAccountNotSet = 18,
+    NoPendingFeeChange = 19,
+    PaymentLimitExceeded = 20,
+    ArithmeticOverflow = 21,
+    PromotionNotFound = 22,
+    MerchantAccountAlreadySet = 23,
+    InvalidMerchantAccount = 24,
+    InvalidMerchantProfile = 25,
+    NoPendingAddressChange = 26,
+    MerchantHasOpenInvoices = 27,
+    MerchantClosed = 28,
+    MerchantInactive = 29,
+    MerchantLimitExceeded = 30,
+    RegistrationRequiresApproval = 31,
+    ApplicationNotFound = 32,
+    StaffBelongsToOtherMerchant = 33,
+    StaffLimitExceeded = 34,
+    TerminalKeyNotFound = 35,
+    SignedInvoiceExpired = 36,
+    NonceAlreadyUsed = 37,
+    MerchantPaused = 38,
+    MerchantNotPaused = 39,
+    InvalidMerchantPause = 40,
+    StakeRequired = 41,
+    StakeLocked = 42,
+    InsufficientStake = 43,
+    StakeNotConfigured = 44,
+    NestedSubMerchant = 45,
+    SubMerchantLimitExceeded = 46,
+    InvalidSplit = 47,
+    TooManyTerminalKeys = 48,
+    InvoiceIndexIncomplete = 49,
+    PlatformHasSubMerchants = 50,
+}
+
+/// `ContractError` is at the 50 case limit of a contract error, so errors
+/// added since then live here and continue its numbering.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SettlementError {
+    SplitEscrowLocked = 51,
+    SplitAlreadyReleased = 52,
 }
@@ -1,4 +1,5 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env};
+use crate::types::{FeeBearer, FeeRounding, MerchantRole, StakeRequirement, VerificationTier};
+use soroban_sdk::{contractevent, Address, BytesN, Env, Vec};
 
 #[contractevent]
 pub struct InitalizedEvent {
@@ -72,6 +73,27 @@ pub fn publish_merchant_account_deployed_event(
     .publish(env);
 }
 
+#[contractevent]
+pub struct MerchantAccountApprovedEvent {
+    pub merchant_id: u64,
+    pub account: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_account_approved_event(
+    env: &Env,
+    merchant_id: u64,
+    account: Address,
+    timestamp: u64,
+) {
+    MerchantAccountApprovedEvent {
+        merchant_id,
+        account,
+        timestamp,
+    }
+    .publish(env);
+}
+
 #[contractevent]
 pub struct MerchantStatusChangedEvent {
     pub merchant_id: u64,
@@ -122,6 +144,8 @@ pub struct InvoiceRefundedEvent {
     pub invoice_id: u64,
     pub merchant: Address,
     pub amount: i128,
+    pub fee_amount: i128,
+    pub fee_bearer: FeeBearer,
     pub timestamp: u64,
 }
 
@@ -130,12 +154,16 @@ pub fn publish_invoice_refunded_event(
     invoice_id: u64,
     merchant: Address,
     amount: i128,
+    fee_amount: i128,
+    fee_bearer: FeeBearer,
     timestamp: u64,
 ) {
     InvoiceRefundedEvent {
         invoice_id,
         merchant,
         amount,
+        fee_amount,
+        fee_bearer,
         timestamp,
     }
     .publish(env);
@@ -147,15 +175,20 @@ pub struct InvoicePartiallyRefundedEvent {
     pub merchant: Address,
     pub amount: i128,
     pub total_amount_refunded: i128,
+    pub fee_amount: i128,
+    pub fee_bearer: FeeBearer,
     pub timestamp: u64,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn publish_invoice_partially_refunded_event(
     env: &Env,
     invoice_id: u64,
     merchant: Address,
     amount: i128,
     total_amount_refunded: i128,
+    fee_amount: i128,
+    fee_bearer: FeeBearer,
     timestamp: u64,
 ) {
     InvoicePartiallyRefundedEvent {
@@ -163,6 +196,8 @@ pub fn publish_invoice_partially_refunded_event(
         merchant,
         amount,
         total_amount_refunded,
+        fee_amount,
+        fee_bearer,
         timestamp,
     }
     .publish(env);
@@ -206,144 +241,1055 @@ pub fn publish_merchant_key_set_event(
 }
 
 #[contractevent]
-pub struct RoleGrantedEvent {
-    pub user: Address,
-    pub role: crate::types::Role,
+pub struct MerchantKeyRotatedEvent {
+    pub merchant: Address,
+    pub old_key: BytesN<32>,
+    pub key: BytesN<32>,
+    pub version: u32,
     pub timestamp: u64,
 }
 
-pub fn publish_role_granted_event(
+pub fn publish_merchant_key_rotated_event(
     env: &Env,
-    user: Address,
-    role: crate::types::Role,
+    merchant: Address,
+    old_key: BytesN<32>,
+    key: BytesN<32>,
+    version: u32,
     timestamp: u64,
 ) {
-    RoleGrantedEvent {
-        user,
-        role,
+    MerchantKeyRotatedEvent {
+        merchant,
+        old_key,
+        key,
+        version,
         timestamp,
     }
     .publish(env);
 }
 
 #[contractevent]
-pub struct RoleRevokedEvent {
-    pub user: Address,
-    pub role: crate::types::Role,
+pub struct MerchantKeyRevokedEvent {
+    pub merchant: Address,
+    pub key: BytesN<32>,
+    pub version: u32,
     pub timestamp: u64,
 }
 
-pub fn publish_role_revoked_event(
+pub fn publish_merchant_key_revoked_event(
     env: &Env,
-    user: Address,
-    role: crate::types::Role,
+    merchant: Address,
+    key: BytesN<32>,
+    version: u32,
     timestamp: u64,
 ) {
-    RoleRevokedEvent {
-        user,
-        role,
+    MerchantKeyRevokedEvent {
+        merchant,
+        key,
+        version,
         timestamp,
     }
     .publish(env);
 }
 
 #[contractevent]
-pub struct ContractPausedEvent {
-    pub admin: Address,
+pub struct MerchantProfileUpdatedEvent {
+    pub merchant_id: u64,
+    pub category: soroban_sdk::String,
+    pub country_code: soroban_sdk::String,
     pub timestamp: u64,
 }
 
-pub fn publish_contract_paused_event(env: &Env, admin: Address, timestamp: u64) {
-    ContractPausedEvent { admin, timestamp }.publish(env);
+pub fn publish_merchant_profile_updated_event(
+    env: &Env,
+    merchant_id: u64,
+    category: soroban_sdk::String,
+    country_code: soroban_sdk::String,
+    timestamp: u64,
+) {
+    MerchantProfileUpdatedEvent {
+        merchant_id,
+        category,
+        country_code,
+        timestamp,
+    }
+    .publish(env);
 }
 
 #[contractevent]
-pub struct ContractUnpausedEvent {
-    pub admin: Address,
+pub struct MerchantAddressProposedEvent {
+    pub merchant_id: u64,
+    pub new_address: Address,
     pub timestamp: u64,
 }
 
-pub fn publish_contract_unpaused_event(env: &Env, admin: Address, timestamp: u64) {
-    ContractUnpausedEvent { admin, timestamp }.publish(env);
+pub fn publish_merchant_address_proposed_event(
+    env: &Env,
+    merchant_id: u64,
+    new_address: Address,
+    timestamp: u64,
+) {
+    MerchantAddressProposedEvent {
+        merchant_id,
+        new_address,
+        timestamp,
+    }
+    .publish(env);
 }
 
 #[contractevent]
-pub struct FeeSetEvent {
+pub struct MerchantAddressChangedEvent {
+    pub merchant_id: u64,
+    pub old_address: Address,
+    pub new_address: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_address_changed_event(
+    env: &Env,
+    merchant_id: u64,
+    old_address: Address,
+    new_address: Address,
+    timestamp: u64,
+) {
+    MerchantAddressChangedEvent {
+        merchant_id,
+        old_address,
+        new_address,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct MerchantClosedEvent {
+    pub merchant_id: u64,
+    pub merchant: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_closed_event(
+    env: &Env,
+    merchant_id: u64,
+    merchant: Address,
+    timestamp: u64,
+) {
+    MerchantClosedEvent {
+        merchant_id,
+        merchant,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct MerchantPausedEvent {
+    pub merchant_id: u64,
+    pub resume_at: Option<u64>,
+    pub message: soroban_sdk::String,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_paused_event(
+    env: &Env,
+    merchant_id: u64,
+    resume_at: Option<u64>,
+    message: soroban_sdk::String,
+    timestamp: u64,
+) {
+    MerchantPausedEvent {
+        merchant_id,
+        resume_at,
+        message,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct MerchantResumedEvent {
+    pub merchant_id: u64,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_resumed_event(env: &Env, merchant_id: u64, timestamp: u64) {
+    MerchantResumedEvent {
+        merchant_id,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct StakeRequirementSetEvent {
+    pub requirement: Option<StakeRequirement>,
+    pub timestamp: u64,
+}
+
+pub fn publish_stake_requirement_set_event(
+    env: &Env,
+    requirement: Option<StakeRequirement>,
+    timestamp: u64,
+) {
+    StakeRequirementSetEvent {
+        requirement,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct StakeDepositedEvent {
+    pub merchant_id: u64,
     pub token: Address,
-    pub fee: i128,
+    pub amount: i128,
     pub timestamp: u64,
 }
 
-pub fn publish_fee_set_event(env: &Env, token: Address, fee: i128, timestamp: u64) {
-    FeeSetEvent {
+pub fn publish_stake_deposited_event(
+    env: &Env,
+    merchant_id: u64,
+    token: Address,
+    amount: i128,
+    timestamp: u64,
+) {
+    StakeDepositedEvent {
+        merchant_id,
         token,
-        fee,
+        amount,
         timestamp,
     }
     .publish(env);
 }
 
 #[contractevent]
-pub struct ContractUpgradedEvent {
-    pub new_wasm_hash: BytesN<32>,
+pub struct StakeSlashedEvent {
+    pub merchant_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub recipient: Address,
     pub timestamp: u64,
 }
 
-pub fn publish_contract_upgraded_event(env: &Env, new_wasm_hash: BytesN<32>, timestamp: u64) {
-    ContractUpgradedEvent {
-        new_wasm_hash,
+pub fn publish_stake_slashed_event(
+    env: &Env,
+    merchant_id: u64,
+    token: Address,
+    amount: i128,
+    recipient: Address,
+    timestamp: u64,
+) {
+    StakeSlashedEvent {
+        merchant_id,
+        token,
+        amount,
+        recipient,
         timestamp,
     }
     .publish(env);
 }
 
 #[contractevent]
-pub struct InvoicePaidEvent {
-    pub invoice_id: u64,
-    pub payer: Address,
+pub struct StakeWithdrawnEvent {
+    pub merchant_id: u64,
+    pub token: Address,
     pub amount: i128,
-    pub fee: i128,
-    pub merchant_amount: i128,
     pub timestamp: u64,
 }
 
-pub fn publish_invoice_paid_event(
+pub fn publish_stake_withdrawn_event(
     env: &Env,
-    invoice_id: u64,
-    payer: Address,
+    merchant_id: u64,
+    token: Address,
     amount: i128,
-    fee: i128,
-    merchant_amount: i128,
     timestamp: u64,
 ) {
-    InvoicePaidEvent {
-        invoice_id,
-        payer,
+    StakeWithdrawnEvent {
+        merchant_id,
+        token,
         amount,
-        fee,
-        merchant_amount,
         timestamp,
     }
     .publish(env);
 }
 
 #[contractevent]
-pub struct InvoiceCancelledEvent {
-    pub invoice_id: u64,
+pub struct SubMerchantRegisteredEvent {
+    pub platform_id: u64,
+    pub merchant_id: u64,
     pub merchant: Address,
     pub timestamp: u64,
 }
 
-pub fn publish_invoice_cancelled_event(
+pub fn publish_sub_merchant_registered_event(
     env: &Env,
-    invoice_id: u64,
+    platform_id: u64,
+    merchant_id: u64,
     merchant: Address,
     timestamp: u64,
 ) {
-    InvoiceCancelledEvent {
-        invoice_id,
+    SubMerchantRegisteredEvent {
+        platform_id,
+        merchant_id,
         merchant,
         timestamp,
     }
     .publish(env);
 }
+
+#[contractevent]
+pub struct SubMerchantRemovedEvent {
+    pub platform_id: u64,
+    pub merchant_id: u64,
+    pub timestamp: u64,
+}
+
+pub fn publish_sub_merchant_removed_event(
+    env: &Env,
+    platform_id: u64,
+    merchant_id: u64,
+    timestamp: u64,
+) {
+    SubMerchantRemovedEvent {
+        platform_id,
+        merchant_id,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct PlatformApprovalSetEvent {
+    pub platform_id: u64,
+    pub approved: bool,
+    pub reviewer: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_platform_approval_set_event(
+    env: &Env,
+    platform_id: u64,
+    approved: bool,
+    reviewer: Address,
+    timestamp: u64,
+) {
+    PlatformApprovalSetEvent {
+        platform_id,
+        approved,
+        reviewer,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct PlatformCommissionSetEvent {
+    pub platform_id: u64,
+    pub commission_bps: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_platform_commission_set_event(
+    env: &Env,
+    platform_id: u64,
+    commission_bps: i128,
+    timestamp: u64,
+) {
+    PlatformCommissionSetEvent {
+        platform_id,
+        commission_bps,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct PlatformCommissionPaidEvent {
+    pub invoice_id: u64,
+    pub platform_id: u64,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_platform_commission_paid_event(
+    env: &Env,
+    invoice_id: u64,
+    platform_id: u64,
+    amount: i128,
+    timestamp: u64,
+) {
+    PlatformCommissionPaidEvent {
+        invoice_id,
+        platform_id,
+        amount,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct CommissionScheduledEvent {
+    pub platform_id: u64,
+    pub current_bps: i128,
+    pub new_bps: i128,
+    pub effective_at: u64,
+    pub timestamp: u64,
+}
+
+pub fn publish_commission_scheduled_event(
+    env: &Env,
+    platform_id: u64,
+    current_bps: i128,
+    new_bps: i128,
+    effective_at: u64,
+    timestamp: u64,
+) {
+    CommissionScheduledEvent {
+        platform_id,
+        current_bps,
+        new_bps,
+        effective_at,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct CommissionRefundedEvent {
+    pub invoice_id: u64,
+    pub platform_id: u64,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_commission_refunded_event(
+    env: &Env,
+    invoice_id: u64,
+    platform_id: u64,
+    amount: i128,
+    timestamp: u64,
+) {
+    CommissionRefundedEvent {
+        invoice_id,
+        platform_id,
+        amount,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct SplitPaidEvent {
+    pub invoice_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_split_paid_event(
+    env: &Env,
+    invoice_id: u64,
+    recipient: Address,
+    amount: i128,
+    timestamp: u64,
+) {
+    SplitPaidEvent {
+        invoice_id,
+        recipient,
+        amount,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct SplitReleasedEvent {
+    pub invoice_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_split_released_event(
+    env: &Env,
+    invoice_id: u64,
+    recipient: Address,
+    amount: i128,
+    timestamp: u64,
+) {
+    SplitReleasedEvent {
+        invoice_id,
+        recipient,
+        amount,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct SplitRefundedEvent {
+    pub invoice_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_split_refunded_event(
+    env: &Env,
+    invoice_id: u64,
+    recipient: Address,
+    amount: i128,
+    timestamp: u64,
+) {
+    SplitRefundedEvent {
+        invoice_id,
+        recipient,
+        amount,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct RoleGrantedEvent {
+    pub user: Address,
+    pub role: crate::types::Role,
+    pub timestamp: u64,
+}
+
+pub fn publish_role_granted_event(
+    env: &Env,
+    user: Address,
+    role: crate::types::Role,
+    timestamp: u64,
+) {
+    RoleGrantedEvent {
+        user,
+        role,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct RoleRevokedEvent {
+    pub user: Address,
+    pub role: crate::types::Role,
+    pub timestamp: u64,
+}
+
+pub fn publish_role_revoked_event(
+    env: &Env,
+    user: Address,
+    role: crate::types::Role,
+    timestamp: u64,
+) {
+    RoleRevokedEvent {
+        user,
+        role,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct ContractPausedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_contract_paused_event(env: &Env, admin: Address, timestamp: u64) {
+    ContractPausedEvent { admin, timestamp }.publish(env);
+}
+
+#[contractevent]
+pub struct ContractUnpausedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_contract_unpaused_event(env: &Env, admin: Address, timestamp: u64) {
+    ContractUnpausedEvent { admin, timestamp }.publish(env);
+}
+
+#[contractevent]
+pub struct FeeSetEvent {
+    pub token: Address,
+    pub fee: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_set_event(env: &Env, token: Address, fee: i128, timestamp: u64) {
+    FeeSetEvent {
+        token,
+        fee,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct FeeChangeScheduledEvent {
+    pub token: Address,
+    pub current_fee: i128,
+    pub new_fee: i128,
+    pub effective_at: u64,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_change_scheduled_event(
+    env: &Env,
+    token: Address,
+    current_fee: i128,
+    new_fee: i128,
+    effective_at: u64,
+    timestamp: u64,
+) {
+    FeeChangeScheduledEvent {
+        token,
+        current_fee,
+        new_fee,
+        effective_at,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct FeeChangeCancelledEvent {
+    pub token: Address,
+    pub fee: i128,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_change_cancelled_event(env: &Env, token: Address, fee: i128, timestamp: u64) {
+    FeeChangeCancelledEvent {
+        token,
+        fee,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct FeeRoundingSetEvent {
+    pub token: Address,
+    pub rounding: FeeRounding,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_rounding_set_event(
+    env: &Env,
+    token: Address,
+    rounding: FeeRounding,
+    timestamp: u64,
+) {
+    FeeRoundingSetEvent {
+        token,
+        rounding,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct FeeRoundingScheduledEvent {
+    pub token: Address,
+    pub current_rounding: FeeRounding,
+    pub new_rounding: FeeRounding,
+    pub effective_at: u64,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_rounding_scheduled_event(
+    env: &Env,
+    token: Address,
+    current_rounding: FeeRounding,
+    new_rounding: FeeRounding,
+    effective_at: u64,
+    timestamp: u64,
+) {
+    FeeRoundingScheduledEvent {
+        token,
+        current_rounding,
+        new_rounding,
+        effective_at,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct PromotionSetEvent {
+    pub token: Address,
+    pub merchant_id: Option<u64>,
+    pub max_age: Option<u64>,
+    pub volume_cap: Option<i128>,
+    pub timestamp: u64,
+}
+
+pub fn publish_promotion_set_event(
+    env: &Env,
+    token: Address,
+    merchant_id: Option<u64>,
+    max_age: Option<u64>,
+    volume_cap: Option<i128>,
+    timestamp: u64,
+) {
+    PromotionSetEvent {
+        token,
+        merchant_id,
+        max_age,
+        volume_cap,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct PromotionRemovedEvent {
+    pub token: Address,
+    pub merchant_id: Option<u64>,
+    pub timestamp: u64,
+}
+
+pub fn publish_promotion_removed_event(
+    env: &Env,
+    token: Address,
+    merchant_id: Option<u64>,
+    timestamp: u64,
+) {
+    PromotionRemovedEvent {
+        token,
+        merchant_id,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct ContractUpgradedEvent {
+    pub new_wasm_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub fn publish_contract_upgraded_event(env: &Env, new_wasm_hash: BytesN<32>, timestamp: u64) {
+    ContractUpgradedEvent {
+        new_wasm_hash,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct InvoicePaidEvent {
+    pub invoice_id: u64,
+    pub payer: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub merchant_amount: i128,
+    pub total_paid: i128,
+    pub fee_bearer: FeeBearer,
+    pub timestamp: u64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn publish_invoice_paid_event(
+    env: &Env,
+    invoice_id: u64,
+    payer: Address,
+    amount: i128,
+    fee: i128,
+    merchant_amount: i128,
+    total_paid: i128,
+    fee_bearer: FeeBearer,
+    timestamp: u64,
+) {
+    InvoicePaidEvent {
+        invoice_id,
+        payer,
+        amount,
+        fee,
+        merchant_amount,
+        total_paid,
+        fee_bearer,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct InvoiceCancelledEvent {
+    pub invoice_id: u64,
+    pub merchant: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_invoice_cancelled_event(
+    env: &Env,
+    invoice_id: u64,
+    merchant: Address,
+    timestamp: u64,
+) {
+    InvoiceCancelledEvent {
+        invoice_id,
+        merchant,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct FeeBearerSetEvent {
+    pub merchant_id: u64,
+    pub fee_bearer: FeeBearer,
+    pub timestamp: u64,
+}
+
+pub fn publish_fee_bearer_set_event(
+    env: &Env,
+    merchant_id: u64,
+    fee_bearer: FeeBearer,
+    timestamp: u64,
+) {
+    FeeBearerSetEvent {
+        merchant_id,
+        fee_bearer,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct MerchantTokensSetEvent {
+    pub merchant_id: u64,
+    pub tokens: Vec<Address>,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_tokens_set_event(
+    env: &Env,
+    merchant_id: u64,
+    tokens: Vec<Address>,
+    timestamp: u64,
+) {
+    MerchantTokensSetEvent {
+        merchant_id,
+        tokens,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct MerchantTierSetEvent {
+    pub merchant_id: u64,
+    pub tier: VerificationTier,
+    pub timestamp: u64,
+}
+
+pub fn publish_merchant_tier_set_event(
+    env: &Env,
+    merchant_id: u64,
+    tier: VerificationTier,
+    timestamp: u64,
+) {
+    MerchantTierSetEvent {
+        merchant_id,
+        tier,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct TierLimitsSetEvent {
+    pub tier: VerificationTier,
+    pub token: Address,
+    pub max_invoice_amount: Option<i128>,
+    pub max_volume_30d: Option<i128>,
+    pub timestamp: u64,
+}
+
+pub fn publish_tier_limits_set_event(
+    env: &Env,
+    tier: VerificationTier,
+    token: Address,
+    max_invoice_amount: Option<i128>,
+    max_volume_30d: Option<i128>,
+    timestamp: u64,
+) {
+    TierLimitsSetEvent {
+        tier,
+        token,
+        max_invoice_amount,
+        max_volume_30d,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct RegistrationApprovalSetEvent {
+    pub required: bool,
+    pub timestamp: u64,
+}
+
+pub fn publish_registration_approval_set_event(env: &Env, required: bool, timestamp: u64) {
+    RegistrationApprovalSetEvent {
+        required,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct ApplicationSubmittedEvent {
+    pub applicant: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_application_submitted_event(env: &Env, applicant: Address, timestamp: u64) {
+    ApplicationSubmittedEvent {
+        applicant,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct ApplicationApprovedEvent {
+    pub applicant: Address,
+    pub merchant_id: u64,
+    pub reviewer: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_application_approved_event(
+    env: &Env,
+    applicant: Address,
+    merchant_id: u64,
+    reviewer: Address,
+    timestamp: u64,
+) {
+    ApplicationApprovedEvent {
+        applicant,
+        merchant_id,
+        reviewer,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct ApplicationRejectedEvent {
+    pub applicant: Address,
+    pub reviewer: Address,
+    pub timestamp: u64,
+}
+
+pub fn publish_application_rejected_event(
+    env: &Env,
+    applicant: Address,
+    reviewer: Address,
+    timestamp: u64,
+) {
+    ApplicationRejectedEvent {
+        applicant,
+        reviewer,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct StaffRoleGrantedEvent {
+    pub merchant_id: u64,
+    pub staff: Address,
+    pub role: MerchantRole,
+    pub refund_limit: Option<i128>,
+    pub timestamp: u64,
+}
+
+pub fn publish_staff_role_granted_event(
+    env: &Env,
+    merchant_id: u64,
+    staff: Address,
+    role: MerchantRole,
+    refund_limit: Option<i128>,
+    timestamp: u64,
+) {
+    StaffRoleGrantedEvent {
+        merchant_id,
+        staff,
+        role,
+        refund_limit,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct StaffRoleRevokedEvent {
+    pub merchant_id: u64,
+    pub staff: Address,
+    pub role: MerchantRole,
+    pub timestamp: u64,
+}
+
+pub fn publish_staff_role_revoked_event(
+    env: &Env,
+    merchant_id: u64,
+    staff: Address,
+    role: MerchantRole,
+    timestamp: u64,
+) {
+    StaffRoleRevokedEvent {
+        merchant_id,
+        staff,
+        role,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct TerminalKeyAddedEvent {
+    pub merchant_id: u64,
+    pub key: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub fn publish_terminal_key_added_event(
+    env: &Env,
+    merchant_id: u64,
+    key: BytesN<32>,
+    timestamp: u64,
+) {
+    TerminalKeyAddedEvent {
+        merchant_id,
+        key,
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent]
+pub struct TerminalKeyRemovedEvent {
+    pub merchant_id: u64,
+    pub key: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub fn publish_terminal_key_removed_event(
+    env: &Env,
+    merchant_id: u64,
+    key: BytesN<32>,
+    timestamp: u64,
+) {
+    TerminalKeyRemovedEvent {
+        merchant_id,
+        key,
+        timestamp,
+    }
+    .publish(env);
+}
@@ -1,4 +1,10 @@
-use crate::types::{Invoice, InvoiceFilter, Merchant, MerchantFilter, Role};
+use crate::types::{
+    FeeBearer, FeeRounding, Invoice, InvoiceFee, InvoiceFilter, LimitHeadroom, Merchant,
+    MerchantApplication, MerchantFilter, MerchantKeyRecord, MerchantPause, MerchantProfile,
+    MerchantRole, MerchantStake, MerchantStats, PendingFee, PendingFeeRounding, Promotion,
+    PromotionAllowance, Role, SignedInvoice, SplitPayout, SplitRecipient, StaffMember,
+    StakeRequirement, TierLimits, VerificationTier,
+};
 use soroban_sdk::{contracttrait, Address, BytesN, Env, String, Vec};
 
 #[contracttrait]
@@ -11,14 +17,61 @@ pub trait ShadeTrait {
     fn set_account_wasm_hash(env: Env, admin: Address, wasm_hash: soroban_sdk::BytesN<32>);
     fn set_fee(env: Env, admin: Address, token: Address, fee: i128);
     fn get_fee(env: Env, token: Address) -> i128;
+    fn get_pending_fee(env: Env, token: Address) -> Option<PendingFee>;
+    fn cancel_fee_change(env: Env, admin: Address, token: Address);
+    fn set_fee_rounding(env: Env, admin: Address, token: Address, rounding: FeeRounding);
+    fn get_fee_rounding(env: Env, token: Address) -> FeeRounding;
+    fn get_pending_fee_rounding(env: Env, token: Address) -> Option<PendingFeeRounding>;
+    fn set_promotion(
+        env: Env,
+        admin: Address,
+        token: Address,
+        merchant_id: Option<u64>,
+        promotion: Promotion,
+    );
+    fn remove_promotion(env: Env, admin: Address, token: Address, merchant_id: Option<u64>);
+    fn get_promotion_allowance(
+        env: Env,
+        merchant_id: u64,
+        token: Address,
+    ) -> Option<PromotionAllowance>;
     fn register_merchant(env: Env, merchant: Address);
+    fn set_registration_approval(env: Env, admin: Address, required: bool);
+    fn requires_registration_approval(env: Env) -> bool;
+    fn submit_merchant_application(env: Env, applicant: Address, profile: MerchantProfile);
+    fn get_merchant_application(env: Env, applicant: Address) -> Option<MerchantApplication>;
+    fn get_pending_merchants(env: Env, start: u32, limit: u32) -> Vec<MerchantApplication>;
+    fn get_pending_merchant_count(env: Env) -> u32;
+    fn approve_merchant_application(env: Env, reviewer: Address, applicant: Address) -> u64;
+    fn reject_merchant_application(env: Env, reviewer: Address, applicant: Address);
     fn get_merchant(env: Env, merchant_id: u64) -> Merchant;
     fn get_merchants(env: Env, filter: MerchantFilter) -> Vec<Merchant>;
+    fn get_merchant_by_address(env: Env, merchant: Address) -> Merchant;
+    fn get_merchant_id(env: Env, merchant: Address) -> u64;
+    fn get_merchant_count(env: Env) -> u64;
     fn is_merchant(env: Env, merchant: Address) -> bool;
     fn set_merchant_status(env: Env, admin: Address, merchant_id: u64, status: bool);
     fn is_merchant_active(env: Env, merchant_id: u64) -> bool;
+    fn is_merchant_closed(env: Env, merchant_id: u64) -> bool;
     fn verify_merchant(env: Env, admin: Address, merchant_id: u64, status: bool);
     fn is_merchant_verified(env: Env, merchant_id: u64) -> bool;
+    fn set_merchant_tier(env: Env, admin: Address, merchant_id: u64, tier: VerificationTier);
+    fn get_merchant_tier(env: Env, merchant_id: u64) -> VerificationTier;
+    fn set_tier_limits(
+        env: Env,
+        admin: Address,
+        tier: VerificationTier,
+        token: Address,
+        limits: TierLimits,
+    );
+    fn get_tier_limits(env: Env, tier: VerificationTier, token: Address) -> TierLimits;
+    fn get_limit_headroom(env: Env, merchant_id: u64, token: Address) -> LimitHeadroom;
+    fn set_merchant_profile(env: Env, merchant: Address, profile: MerchantProfile);
+    fn get_merchant_profile(env: Env, merchant_id: u64) -> Option<MerchantProfile>;
+    fn change_merchant_address(env: Env, merchant: Address, new_address: Address);
+    fn recover_merchant_address(env: Env, admin: Address, merchant_id: u64, new_address: Address);
+    fn accept_merchant_address(env: Env, new_address: Address, merchant_id: u64);
+    fn close_merchant(env: Env, merchant: Address);
     fn create_invoice(
         env: Env,
         merchant: Address,
@@ -27,20 +80,104 @@ pub trait ShadeTrait {
         token: Address,
     ) -> u64;
     fn get_invoice(env: Env, invoice_id: u64) -> Invoice;
+    fn get_invoice_fee(env: Env, invoice_id: u64) -> InvoiceFee;
     fn refund_invoice(env: Env, merchant: Address, invoice_id: u64);
     fn set_merchant_key(env: Env, merchant: Address, key: BytesN<32>);
     fn get_merchant_key(env: Env, merchant: Address) -> BytesN<32>;
+    fn revoke_merchant_key(env: Env, merchant: Address);
+    fn get_merchant_keys(env: Env, merchant_id: u64) -> Vec<MerchantKeyRecord>;
+    fn get_merchant_key_at(env: Env, merchant_id: u64, timestamp: u64) -> BytesN<32>;
+    fn add_terminal_key(env: Env, merchant: Address, key: BytesN<32>);
+    fn remove_terminal_key(env: Env, merchant: Address, key: BytesN<32>);
+    fn get_terminal_keys(env: Env, merchant_id: u64) -> Vec<BytesN<32>>;
     fn grant_role(env: Env, admin: Address, user: Address, role: Role);
     fn revoke_role(env: Env, admin: Address, user: Address, role: Role);
     fn has_role(env: Env, user: Address, role: Role) -> bool;
     fn get_invoices(env: Env, filter: InvoiceFilter) -> Vec<Invoice>;
-    fn refund_invoice_partial(env: Env, invoice_id: u64, amount: i128);
+    fn refund_invoice_partial(env: Env, caller: Address, invoice_id: u64, amount: i128);
     fn pause(env: Env, admin: Address);
     fn unpause(env: Env, admin: Address);
     fn is_paused(env: Env) -> bool;
     fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
     fn set_merchant_account(env: Env, merchant: Address, account: Address);
     fn get_merchant_account(env: Env, merchant_id: u64) -> Address;
+    fn approve_merchant_account(env: Env, admin: Address, merchant_id: u64, account: Address);
+    fn deploy_merchant_account(env: Env, merchant: Address) -> Address;
+    fn migrate_merchant_accounts(env: Env, admin: Address, start_id: u64, limit: u32) -> u32;
+    fn migrate_merchant_invoices(env: Env, admin: Address, merchant_id: u64, limit: u32) -> bool;
+    fn add_merchant_account_token(env: Env, caller: Address, merchant_id: u64, token: Address);
+    fn verify_merchant_account(env: Env, caller: Address, merchant_id: u64);
+    fn restrict_merchant_account(env: Env, caller: Address, merchant_id: u64, status: bool);
     fn pay_invoice(env: Env, payer: Address, invoice_id: u64);
+    fn pay_signed_invoice(
+        env: Env,
+        payer: Address,
+        signed_invoice: SignedInvoice,
+        terminal_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> u64;
+    fn pay_invoice_with_limits(
+        env: Env,
+        payer: Address,
+        invoice_id: u64,
+        max_total: Option<i128>,
+        max_fee: Option<i128>,
+    );
     fn void_invoice(env: Env, merchant: Address, invoice_id: u64);
+    fn grant_merchant_role(
+        env: Env,
+        merchant: Address,
+        staff: Address,
+        role: MerchantRole,
+        refund_limit: Option<i128>,
+    );
+    fn revoke_merchant_role(env: Env, merchant: Address, staff: Address, role: MerchantRole);
+    fn get_staff_member(env: Env, staff: Address) -> Option<StaffMember>;
+    fn has_merchant_role(env: Env, staff: Address, role: MerchantRole) -> bool;
+    fn set_fee_bearer(env: Env, merchant: Address, fee_bearer: FeeBearer);
+    fn get_fee_bearer(env: Env, merchant_id: u64) -> FeeBearer;
+    fn set_merchant_tokens(env: Env, merchant: Address, tokens: Vec<Address>);
+    fn get_merchant_tokens(env: Env, merchant_id: u64) -> Vec<Address>;
+    fn is_merchant_token(env: Env, merchant_id: u64, token: Address) -> bool;
+    fn get_merchant_stats(env: Env, merchant_id: u64) -> MerchantStats;
+    fn pause_merchant(env: Env, merchant: Address, resume_at: Option<u64>, message: String);
+    fn resume_merchant(env: Env, merchant: Address);
+    fn get_merchant_pause(env: Env, merchant_id: u64) -> Option<MerchantPause>;
+    fn is_merchant_paused(env: Env, merchant_id: u64) -> bool;
+    fn set_stake_requirement(env: Env, admin: Address, requirement: Option<StakeRequirement>);
+    fn get_stake_requirement(env: Env) -> Option<StakeRequirement>;
+    fn deposit_stake(env: Env, merchant: Address, amount: i128);
+    fn slash_stake(env: Env, caller: Address, merchant_id: u64, amount: i128, recipient: Address);
+    fn withdraw_stake(env: Env, merchant_id: u64);
+    fn get_merchant_stake(env: Env, merchant_id: u64) -> Option<MerchantStake>;
+    fn register_sub_merchant(env: Env, platform: Address, sub_merchant: Address) -> u64;
+    fn remove_sub_merchant(env: Env, platform: Address, sub_merchant_id: u64);
+    fn set_platform_approval(env: Env, reviewer: Address, platform_id: u64, approved: bool);
+    fn is_approved_platform(env: Env, platform_id: u64) -> bool;
+    fn get_parent_merchant(env: Env, merchant_id: u64) -> Option<u64>;
+    fn get_sub_merchants(env: Env, platform_id: u64) -> Vec<u64>;
+    fn set_platform_commission(env: Env, platform: Address, commission_bps: i128);
+    fn get_platform_commission(env: Env, platform_id: u64) -> i128;
+    fn get_pending_platform_commission(env: Env, platform_id: u64) -> Option<PendingFee>;
+    fn create_sub_merchant_invoice(
+        env: Env,
+        platform: Address,
+        sub_merchant_id: u64,
+        description: String,
+        amount: i128,
+        token: Address,
+    ) -> u64;
+    fn get_platform_stats(env: Env, platform_id: u64) -> MerchantStats;
+    fn create_split_invoice(
+        env: Env,
+        merchant: Address,
+        description: String,
+        amount: i128,
+        token: Address,
+        split: Vec<SplitRecipient>,
+    ) -> u64;
+    fn get_invoice_split(env: Env, invoice_id: u64) -> Vec<SplitRecipient>;
+    fn get_split_payouts(env: Env, invoice_id: u64) -> Vec<SplitPayout>;
+    fn release_split_payouts(env: Env, invoice_id: u64);
+    fn is_split_released(env: Env, invoice_id: u64) -> bool;
 }
@@ -1,12 +1,21 @@
 use crate::components::{
-    access_control as access_control_component, admin as admin_component, core as core_component,
-    invoice as invoice_component, merchant as merchant_component, pausable as pausable_component,
-    upgrade as upgrade_component,
+    access_control as access_control_component, admin as admin_component,
+    application as application_component, core as core_component, invoice as invoice_component,
+    limits as limits_component, merchant as merchant_component, pausable as pausable_component,
+    platform as platform_component, promotion as promotion_component, split as split_component,
+    staff as staff_component, stake as stake_component, stats as stats_component,
+    terminal as terminal_component, upgrade as upgrade_component,
 };
 use crate::errors::ContractError;
 use crate::events;
 use crate::interface::ShadeTrait;
-use crate::types::{ContractInfo, DataKey, Invoice, InvoiceFilter, Merchant, MerchantFilter, Role};
+use crate::types::{
+    ContractInfo, DataKey, FeeBearer, FeeRounding, Invoice, InvoiceFee, InvoiceFilter,
+    LimitHeadroom, Merchant, MerchantApplication, MerchantFilter, MerchantKeyRecord, MerchantPause,
+    MerchantProfile, MerchantRole, MerchantStake, MerchantStats, PendingFee, PendingFeeRounding,
+    Promotion, PromotionAllowance, Role, SignedInvoice, SplitPayout, SplitRecipient, StaffMember,
+    StakeRequirement, TierLimits, VerificationTier,
+};
 use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Vec};
 
 #[contract]
@@ -59,11 +68,92 @@ impl ShadeTrait for Shade {
         admin_component::get_fee(&env, &token)
     }
 
+    fn get_pending_fee(env: Env, token: Address) -> Option<PendingFee> {
+        admin_component::get_pending_fee(&env, &token)
+    }
+
+    fn cancel_fee_change(env: Env, admin: Address, token: Address) {
+        pausable_component::assert_not_paused(&env);
+        admin_component::cancel_fee_change(&env, &admin, &token);
+    }
+
+    fn set_fee_rounding(env: Env, admin: Address, token: Address, rounding: FeeRounding) {
+        pausable_component::assert_not_paused(&env);
+        admin_component::set_fee_rounding(&env, &admin, &token, rounding);
+    }
+
+    fn get_fee_rounding(env: Env, token: Address) -> FeeRounding {
+        admin_component::get_fee_rounding(&env, &token)
+    }
+
+    fn get_pending_fee_rounding(env: Env, token: Address) -> Option<PendingFeeRounding> {
+        admin_component::get_pending_fee_rounding(&env, &token)
+    }
+
+    fn set_promotion(
+        env: Env,
+        admin: Address,
+        token: Address,
+        merchant_id: Option<u64>,
+        promotion: Promotion,
+    ) {
+        pausable_component::assert_not_paused(&env);
+        promotion_component::set_promotion(&env, &admin, &token, merchant_id, &promotion);
+    }
+
+    fn remove_promotion(env: Env, admin: Address, token: Address, merchant_id: Option<u64>) {
+        pausable_component::assert_not_paused(&env);
+        promotion_component::remove_promotion(&env, &admin, &token, merchant_id);
+    }
+
+    fn get_promotion_allowance(
+        env: Env,
+        merchant_id: u64,
+        token: Address,
+    ) -> Option<PromotionAllowance> {
+        promotion_component::get_promotion_allowance(&env, merchant_id, &token)
+    }
+
     fn register_merchant(env: Env, merchant: Address) {
         pausable_component::assert_not_paused(&env);
         merchant_component::register_merchant(&env, &merchant);
     }
 
+    fn set_registration_approval(env: Env, admin: Address, required: bool) {
+        application_component::set_approval_required(&env, &admin, required);
+    }
+
+    fn requires_registration_approval(env: Env) -> bool {
+        application_component::is_approval_required(&env)
+    }
+
+    fn submit_merchant_application(env: Env, applicant: Address, profile: MerchantProfile) {
+        pausable_component::assert_not_paused(&env);
+        application_component::submit_application(&env, &applicant, profile);
+    }
+
+    fn get_merchant_application(env: Env, applicant: Address) -> Option<MerchantApplication> {
+        application_component::get_application(&env, &applicant)
+    }
+
+    fn get_pending_merchants(env: Env, start: u32, limit: u32) -> Vec<MerchantApplication> {
+        application_component::get_pending_merchants(&env, start, limit)
+    }
+
+    fn get_pending_merchant_count(env: Env) -> u32 {
+        application_component::get_pending_merchant_count(&env)
+    }
+
+    fn approve_merchant_application(env: Env, reviewer: Address, applicant: Address) -> u64 {
+        pausable_component::assert_not_paused(&env);
+        application_component::approve_application(&env, &reviewer, &applicant)
+    }
+
+    fn reject_merchant_application(env: Env, reviewer: Address, applicant: Address) {
+        pausable_component::assert_not_paused(&env);
+        application_component::reject_application(&env, &reviewer, &applicant);
+    }
+
     fn get_merchant(env: Env, merchant_id: u64) -> Merchant {
         merchant_component::get_merchant(&env, merchant_id)
     }
@@ -72,6 +162,18 @@ impl ShadeTrait for Shade {
         merchant_component::get_merchants(&env, filter)
     }
 
+    fn get_merchant_by_address(env: Env, merchant: Address) -> Merchant {
+        merchant_component::get_merchant_by_address(&env, &merchant)
+    }
+
+    fn get_merchant_id(env: Env, merchant: Address) -> u64 {
+        merchant_component::get_merchant_id(&env, &merchant)
+    }
+
+    fn get_merchant_count(env: Env) -> u64 {
+        merchant_component::get_merchant_count(&env)
+    }
+
     fn is_merchant(env: Env, merchant: Address) -> bool {
         merchant_component::is_merchant(&env, &merchant)
     }
@@ -84,6 +186,10 @@ impl ShadeTrait for Shade {
         merchant_component::is_merchant_active(&env, merchant_id)
     }
 
+    fn is_merchant_closed(env: Env, merchant_id: u64) -> bool {
+        merchant_component::is_merchant_closed(&env, merchant_id)
+    }
+
     fn verify_merchant(env: Env, admin: Address, merchant_id: u64, status: bool) {
         merchant_component::verify_merchant(&env, &admin, merchant_id, status);
     }
@@ -92,6 +198,60 @@ impl ShadeTrait for Shade {
         merchant_component::is_merchant_verified(&env, merchant_id)
     }
 
+    fn set_merchant_tier(env: Env, admin: Address, merchant_id: u64, tier: VerificationTier) {
+        merchant_component::set_merchant_tier(&env, &admin, merchant_id, tier);
+    }
+
+    fn get_merchant_tier(env: Env, merchant_id: u64) -> VerificationTier {
+        merchant_component::get_merchant_tier(&env, merchant_id)
+    }
+
+    fn set_tier_limits(
+        env: Env,
+        admin: Address,
+        tier: VerificationTier,
+        token: Address,
+        limits: TierLimits,
+    ) {
+        limits_component::set_tier_limits(&env, &admin, tier, &token, &limits);
+    }
+
+    fn get_tier_limits(env: Env, tier: VerificationTier, token: Address) -> TierLimits {
+        limits_component::get_tier_limits(&env, tier, &token)
+    }
+
+    fn get_limit_headroom(env: Env, merchant_id: u64, token: Address) -> LimitHeadroom {
+        limits_component::get_limit_headroom(&env, merchant_id, &token)
+    }
+
+    fn set_merchant_profile(env: Env, merchant: Address, profile: MerchantProfile) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::set_merchant_profile(&env, &merchant, profile);
+    }
+
+    fn get_merchant_profile(env: Env, merchant_id: u64) -> Option<MerchantProfile> {
+        merchant_component::get_merchant_profile(&env, merchant_id)
+    }
+
+    fn change_merchant_address(env: Env, merchant: Address, new_address: Address) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::change_merchant_address(&env, &merchant, &new_address);
+    }
+
+    fn recover_merchant_address(env: Env, admin: Address, merchant_id: u64, new_address: Address) {
+        merchant_component::recover_merchant_address(&env, &admin, merchant_id, &new_address);
+    }
+
+    fn accept_merchant_address(env: Env, new_address: Address, merchant_id: u64) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::accept_merchant_address(&env, &new_address, merchant_id);
+    }
+
+    fn close_merchant(env: Env, merchant: Address) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::close_merchant(&env, &merchant);
+    }
+
     fn create_invoice(
         env: Env,
         merchant: Address,
@@ -107,6 +267,10 @@ impl ShadeTrait for Shade {
         invoice_component::get_invoice(&env, invoice_id)
     }
 
+    fn get_invoice_fee(env: Env, invoice_id: u64) -> InvoiceFee {
+        invoice_component::get_invoice_fee(&env, invoice_id)
+    }
+
     fn refund_invoice(env: Env, merchant: Address, invoice_id: u64) {
         pausable_component::assert_not_paused(&env);
         invoice_component::refund_invoice(&env, &merchant, invoice_id);
@@ -120,6 +284,31 @@ impl ShadeTrait for Shade {
         merchant_component::get_merchant_key(&env, &merchant)
     }
 
+    fn revoke_merchant_key(env: Env, merchant: Address) {
+        merchant_component::revoke_merchant_key(&env, &merchant);
+    }
+
+    fn get_merchant_keys(env: Env, merchant_id: u64) -> Vec<MerchantKeyRecord> {
+        merchant_component::get_merchant_keys(&env, merchant_id)
+    }
+
+    fn get_merchant_key_at(env: Env, merchant_id: u64, timestamp: u64) -> BytesN<32> {
+        merchant_component::get_merchant_key_at(&env, merchant_id, timestamp)
+    }
+
+    fn add_terminal_key(env: Env, merchant: Address, key: BytesN<32>) {
+        pausable_component::assert_not_paused(&env);
+        terminal_component::add_terminal_key(&env, &merchant, &key);
+    }
+
+    fn remove_terminal_key(env: Env, merchant: Address, key: BytesN<32>) {
+        terminal_component::remove_terminal_key(&env, &merchant, &key);
+    }
+
+    fn get_terminal_keys(env: Env, merchant_id: u64) -> Vec<BytesN<32>> {
+        terminal_component::get_terminal_keys(&env, merchant_id)
+    }
+
     fn grant_role(env: Env, admin: Address, user: Address, role: Role) {
         access_control_component::grant_role(&env, &admin, &user, role);
     }
@@ -136,9 +325,9 @@ impl ShadeTrait for Shade {
         invoice_component::get_invoices(&env, filter)
     }
 
-    fn refund_invoice_partial(env: Env, invoice_id: u64, amount: i128) {
+    fn refund_invoice_partial(env: Env, caller: Address, invoice_id: u64, amount: i128) {
         pausable_component::assert_not_paused(&env);
-        invoice_component::refund_invoice_partial(&env, invoice_id, amount);
+        invoice_component::refund_invoice_partial(&env, &caller, invoice_id, amount);
     }
 
     fn pause(env: Env, admin: Address) {
@@ -165,13 +354,265 @@ impl ShadeTrait for Shade {
         merchant_component::get_merchant_account(&env, merchant_id)
     }
 
+    fn approve_merchant_account(env: Env, admin: Address, merchant_id: u64, account: Address) {
+        merchant_component::approve_merchant_account(&env, &admin, merchant_id, &account);
+    }
+
+    fn deploy_merchant_account(env: Env, merchant: Address) -> Address {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::deploy_merchant_account(&env, &merchant)
+    }
+
+    fn migrate_merchant_accounts(env: Env, admin: Address, start_id: u64, limit: u32) -> u32 {
+        merchant_component::migrate_merchant_accounts(&env, &admin, start_id, limit)
+    }
+
+    fn migrate_merchant_invoices(env: Env, admin: Address, merchant_id: u64, limit: u32) -> bool {
+        invoice_component::migrate_merchant_invoices(&env, &admin, merchant_id, limit)
+    }
+
+    fn add_merchant_account_token(env: Env, caller: Address, merchant_id: u64, token: Address) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::add_merchant_account_token(&env, &caller, merchant_id, &token);
+    }
+
+    fn verify_merchant_account(env: Env, caller: Address, merchant_id: u64) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::verify_merchant_account(&env, &caller, merchant_id);
+    }
+
+    fn restrict_merchant_account(env: Env, caller: Address, merchant_id: u64, status: bool) {
+        merchant_component::restrict_merchant_account(&env, &caller, merchant_id, status);
+    }
+
     fn pay_invoice(env: Env, payer: Address, invoice_id: u64) {
         pausable_component::assert_not_paused(&env);
         invoice_component::pay_invoice(&env, &payer, invoice_id);
     }
 
+    fn pay_signed_invoice(
+        env: Env,
+        payer: Address,
+        signed_invoice: SignedInvoice,
+        terminal_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> u64 {
+        pausable_component::assert_not_paused(&env);
+        invoice_component::pay_signed_invoice(
+            &env,
+            &payer,
+            &signed_invoice,
+            &terminal_key,
+            &signature,
+        )
+    }
+
+    fn pay_invoice_with_limits(
+        env: Env,
+        payer: Address,
+        invoice_id: u64,
+        max_total: Option<i128>,
+        max_fee: Option<i128>,
+    ) {
+        pausable_component::assert_not_paused(&env);
+        invoice_component::pay_invoice_with_limits(&env, &payer, invoice_id, max_total, max_fee);
+    }
+
     fn void_invoice(env: Env, merchant: Address, invoice_id: u64) {
         pausable_component::assert_not_paused(&env);
         invoice_component::void_invoice(&env, &merchant, invoice_id);
     }
+
+    fn grant_merchant_role(
+        env: Env,
+        merchant: Address,
+        staff: Address,
+        role: MerchantRole,
+        refund_limit: Option<i128>,
+    ) {
+        pausable_component::assert_not_paused(&env);
+        staff_component::grant_merchant_role(&env, &merchant, &staff, role, refund_limit);
+    }
+
+    fn revoke_merchant_role(env: Env, merchant: Address, staff: Address, role: MerchantRole) {
+        staff_component::revoke_merchant_role(&env, &merchant, &staff, role);
+    }
+
+    fn get_staff_member(env: Env, staff: Address) -> Option<StaffMember> {
+        staff_component::get_staff_member(&env, &staff)
+    }
+
+    fn has_merchant_role(env: Env, staff: Address, role: MerchantRole) -> bool {
+        staff_component::has_merchant_role(&env, &staff, role)
+    }
+
+    fn set_fee_bearer(env: Env, merchant: Address, fee_bearer: FeeBearer) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::set_fee_bearer(&env, &merchant, fee_bearer);
+    }
+
+    fn get_fee_bearer(env: Env, merchant_id: u64) -> FeeBearer {
+        merchant_component::get_fee_bearer(&env, merchant_id)
+    }
+
+    fn set_merchant_tokens(env: Env, merchant: Address, tokens: Vec<Address>) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::set_merchant_tokens(&env, &merchant, tokens);
+    }
+
+    fn get_merchant_tokens(env: Env, merchant_id: u64) -> Vec<Address> {
+        merchant_component::get_merchant_tokens(&env, merchant_id)
+    }
+
+    fn is_merchant_token(env: Env, merchant_id: u64, token: Address) -> bool {
+        merchant_component::is_merchant_token(&env, merchant_id, &token)
+    }
+
+    fn get_merchant_stats(env: Env, merchant_id: u64) -> MerchantStats {
+        stats_component::get_merchant_stats(&env, merchant_id)
+    }
+
+    fn pause_merchant(env: Env, merchant: Address, resume_at: Option<u64>, message: String) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::pause_merchant(&env, &merchant, resume_at, message);
+    }
+
+    fn resume_merchant(env: Env, merchant: Address) {
+        pausable_component::assert_not_paused(&env);
+        merchant_component::resume_merchant(&env, &merchant);
+    }
+
+    fn get_merchant_pause(env: Env, merchant_id: u64) -> Option<MerchantPause> {
+        merchant_component::get_merchant_pause(&env, merchant_id)
+    }
+
+    fn is_merchant_paused(env: Env, merchant_id: u64) -> bool {
+        merchant_component::is_merchant_paused(&env, merchant_id)
+    }
+
+    fn set_stake_requirement(env: Env, admin: Address, requirement: Option<StakeRequirement>) {
+        stake_component::set_stake_requirement(&env, &admin, &requirement);
+    }
+
+    fn get_stake_requirement(env: Env) -> Option<StakeRequirement> {
+        stake_component::get_stake_requirement(&env)
+    }
+
+    fn deposit_stake(env: Env, merchant: Address, amount: i128) {
+        pausable_component::assert_not_paused(&env);
+        stake_component::deposit_stake(&env, &merchant, amount);
+    }
+
+    fn slash_stake(env: Env, caller: Address, merchant_id: u64, amount: i128, recipient: Address) {
+        pausable_component::assert_not_paused(&env);
+        stake_component::slash_stake(&env, &caller, merchant_id, amount, &recipient);
+    }
+
+    fn withdraw_stake(env: Env, merchant_id: u64) {
+        pausable_component::assert_not_paused(&env);
+        stake_component::withdraw_stake(&env, merchant_id);
+    }
+
+    fn get_merchant_stake(env: Env, merchant_id: u64) -> Option<MerchantStake> {
+        stake_component::get_merchant_stake(&env, merchant_id)
+    }
+
+    fn register_sub_merchant(env: Env, platform: Address, sub_merchant: Address) -> u64 {
+        pausable_component::assert_not_paused(&env);
+        platform_component::register_sub_merchant(&env, &platform, &sub_merchant)
+    }
+
+    fn remove_sub_merchant(env: Env, platform: Address, sub_merchant_id: u64) {
+        pausable_component::assert_not_paused(&env);
+        platform_component::remove_sub_merchant(&env, &platform, sub_merchant_id);
+    }
+
+    fn set_platform_approval(env: Env, reviewer: Address, platform_id: u64, approved: bool) {
+        pausable_component::assert_not_paused(&env);
+        platform_component::set_platform_approval(&env, &reviewer, platform_id, approved);
+    }
+
+    fn is_approved_platform(env: Env, platform_id: u64) -> bool {
+        platform_component::is_approved_platform(&env, platform_id)
+    }
+
+    fn get_parent_merchant(env: Env, merchant_id: u64) -> Option<u64> {
+        platform_component::get_parent_merchant(&env, merchant_id)
+    }
+
+    fn get_sub_merchants(env: Env, platform_id: u64) -> Vec<u64> {
+        platform_component::get_sub_merchants(&env, platform_id)
+    }
+
+    fn set_platform_commission(env: Env, platform: Address, commission_bps: i128) {
+        pausable_component::assert_not_paused(&env);
+        platform_component::set_platform_commission(&env, &platform, commission_bps);
+    }
+
+    fn get_platform_commission(env: Env, platform_id: u64) -> i128 {
+        platform_component::get_platform_commission(&env, platform_id)
+    }
+
+    fn get_pending_platform_commission(env: Env, platform_id: u64) -> Option<PendingFee> {
+        platform_component::get_pending_platform_commission(&env, platform_id)
+    }
+
+    fn create_sub_merchant_invoice(
+        env: Env,
+        platform: Address,
+        sub_merchant_id: u64,
+        description: String,
+        amount: i128,
+        token: Address,
+    ) -> u64 {
+        pausable_component::assert_not_paused(&env);
+        invoice_component::create_sub_merchant_invoice(
+            &env,
+            &platform,
+            sub_merchant_id,
+            &description,
+            amount,
+            &token,
+        )
+    }
+
+    fn get_platform_stats(env: Env, platform_id: u64) -> MerchantStats {
+        platform_component::get_platform_stats(&env, platform_id)
+    }
+
+    fn create_split_invoice(
+        env: Env,
+        merchant: Address,
+        description: String,
+        amount: i128,
+        token: Address,
+        split: Vec<SplitRecipient>,
+    ) -> u64 {
+        pausable_component::assert_not_paused(&env);
+        invoice_component::create_split_invoice(
+            &env,
+            &merchant,
+            &description,
+            amount,
+            &token,
+            &split,
+        )
+    }
+
+    fn get_invoice_split(env: Env, invoice_id: u64) -> Vec<SplitRecipient> {
+        split_component::get_invoice_split(&env, invoice_id)
+    }
+
+    fn get_split_payouts(env: Env, invoice_id: u64) -> Vec<SplitPayout> {
+        split_component::get_split_payouts(&env, invoice_id)
+    }
+
+    fn release_split_payouts(env: Env, invoice_id: u64) {
+        pausable_component::assert_not_paused(&env);
+        split_component::release_split_payouts(&env, invoice_id);
+    }
+
+    fn is_split_released(env: Env, invoice_id: u64) -> bool {
+        split_component::is_split_released(&env, invoice_id)
+    }
 }
@@ -1,11 +1,35 @@
 pub mod test;
 pub mod test_accepted_tokens;
 pub mod test_access_control;
+pub mod test_fee_bearer;
+pub mod test_fee_rounding;
+pub mod test_fees;
 pub mod test_invoice;
 pub mod test_merchant;
+pub mod test_merchant_account;
 pub mod test_merchant_activation;
+pub mod test_merchant_address;
+pub mod test_merchant_application;
+pub mod test_merchant_closure;
 pub mod test_merchant_key;
+pub mod test_merchant_lookup;
+pub mod test_merchant_pause;
+pub mod test_merchant_profile;
+pub mod test_merchant_staff;
+pub mod test_merchant_stake;
+pub mod test_merchant_stats;
+pub mod test_merchant_tiers;
+pub mod test_merchant_tokens;
 pub mod test_merchant_verification;
 pub mod test_pausable;
 pub mod test_payment;
+pub mod test_payment_split;
+pub mod test_promotion;
+pub mod test_refund;
+pub mod test_sub_merchant;
+pub mod test_terminal;
 pub mod test_upgrade;
+
+/// Account contract deployed by the merchant account tests. Regenerate it
+/// with `make account-fixture` whenever `contracts/account` changes.
+pub const ACCOUNT_WASM: &[u8] = include_bytes!("fixtures/account_contract.wasm");
@@ -0,0 +1,262 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{FeeBearer, InvoiceDataKey, InvoiceStatus};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, Map, String, Symbol, TryIntoVal, Val};
+
+fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let shade_contract_id = env.register(Shade, ());
+    let shade_client = ShadeClient::new(&env, &shade_contract_id);
+
+    let admin = Address::generate(&env);
+    shade_client.initialize(&admin);
+
+    let account_wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    shade_client.set_account_wasm_hash(&admin, &account_wasm_hash);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin.clone());
+
+    shade_client.add_accepted_token(&admin, &token.address());
+
+    // Set fee to 500 bps (5%)
+    shade_client.set_fee(&admin, &token.address(), &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    (env, shade_client, shade_contract_id, admin, token.address())
+}
+
+fn register_merchant_with_account(env: &Env, client: &ShadeClient<'_>) -> (Address, Address) {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+
+    let merchant_account = client.deploy_merchant_account(&merchant);
+
+    (merchant, merchant_account)
+}
+
+#[test]
+fn test_fee_bearer_defaults_to_merchant() {
+    let (env, client, _contract_id, _admin, _token) = setup_test_with_payment();
+    let (_merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    assert_eq!(client.get_fee_bearer(&1), FeeBearer::Merchant);
+}
+
+#[test]
+fn test_set_fee_bearer_emits_event() {
+    let (env, client, contract_id, _admin, _token) = setup_test_with_payment();
+    let (merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(event_name, Symbol::new(&env, "fee_bearer_set_event"));
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let merchant_id: u64 = data_map
+        .get(Symbol::new(&env, "merchant_id"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let fee_bearer: FeeBearer = data_map
+        .get(Symbol::new(&env, "fee_bearer"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(merchant_id, 1);
+    assert_eq!(fee_bearer, FeeBearer::Payer);
+
+    assert_eq!(client.get_fee_bearer(&1), FeeBearer::Payer);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_set_fee_bearer_unregistered_merchant() {
+    let (env, client, _contract_id, _admin, _token) = setup_test_with_payment();
+
+    let stranger = Address::generate(&env);
+    client.set_fee_bearer(&stranger, &FeeBearer::Payer);
+}
+
+#[test]
+fn test_payer_pays_fee_on_top() {
+    let (env, client, shade_contract_id, _admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) = register_merchant_with_account(&env, &client);
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let description = String::from_str(&env, "Payer pays fee");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1050);
+
+    client.pay_invoice(&customer, &invoice_id);
+
+    let events = env.events().all();
+    let (_event_contract_id, _topics, data) = events.get(events.len() - 1).unwrap();
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let total_paid: i128 = data_map
+        .get(Symbol::new(&env, "total_paid"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let merchant_amount: i128 = data_map
+        .get(Symbol::new(&env, "merchant_amount"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let fee: i128 = data_map
+        .get(Symbol::new(&env, "fee"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(total_paid, 1050);
+    assert_eq!(merchant_amount, 1000);
+    assert_eq!(fee, 50);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 50);
+    assert_eq!(token_client.balance(&merchant_account), 1000);
+    assert_eq!(token_client.balance(&customer), 0);
+
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+    let invoice_fee = client.get_invoice_fee(&invoice_id);
+    assert_eq!(invoice_fee.fee_bearer, FeeBearer::Payer);
+    assert_eq!(invoice_fee.fee_amount, 50);
+}
+
+#[test]
+#[should_panic]
+fn test_payer_pays_fee_insufficient_funds() {
+    let (env, client, _contract_id, _admin, token) = setup_test_with_payment();
+    let (merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let description = String::from_str(&env, "Payer pays fee");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Only the invoice amount, not the fee on top
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1000);
+
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+#[test]
+fn test_fee_bearer_is_fixed_at_invoice_creation() {
+    let (env, client, shade_contract_id, _admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) = register_merchant_with_account(&env, &client);
+
+    let description = String::from_str(&env, "Merchant pays fee");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Switching after creation does not affect the existing invoice
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1000);
+
+    client.pay_invoice(&customer, &invoice_id);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 50);
+    assert_eq!(token_client.balance(&merchant_account), 950);
+
+    let invoice_fee = client.get_invoice_fee(&invoice_id);
+    assert_eq!(invoice_fee.fee_bearer, FeeBearer::Merchant);
+    assert_eq!(invoice_fee.fee_amount, 50);
+}
+
+#[test]
+fn test_invoice_without_fee_terms_defaults_to_merchant() {
+    let (env, client, shade_contract_id, _admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) = register_merchant_with_account(&env, &client);
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let description = String::from_str(&env, "Legacy invoice");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Invoices stored before fee bearers existed carry no fee terms
+    env.as_contract(&shade_contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&InvoiceDataKey::Fee(invoice_id));
+    });
+    let invoice_fee = client.get_invoice_fee(&invoice_id);
+    assert_eq!(invoice_fee.fee_bearer, FeeBearer::Merchant);
+    assert_eq!(invoice_fee.fee_amount, 0);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1000);
+    client.pay_invoice(&customer, &invoice_id);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&merchant_account), 950);
+    assert_eq!(client.get_invoice_fee(&invoice_id).fee_amount, 50);
+}
+
+#[test]
+fn test_refund_events_carry_fee_terms() {
+    let (env, client, contract_id, _admin, token) = setup_test_with_payment();
+    let (merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let description = String::from_str(&env, "Refunded with fee");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1050);
+    client.pay_invoice(&customer, &invoice_id);
+
+    client.refund_invoice_partial(&merchant, &invoice_id, &400);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(
+        event_name,
+        Symbol::new(&env, "invoice_partially_refunded_event")
+    );
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let fee_amount: i128 = data_map
+        .get(Symbol::new(&env, "fee_amount"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let fee_bearer: FeeBearer = data_map
+        .get(Symbol::new(&env, "fee_bearer"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(fee_amount, 50);
+    assert_eq!(fee_bearer, FeeBearer::Payer);
+
+    client.refund_invoice(&merchant, &invoice_id);
+
+    let events = env.events().all();
+    let (_event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(event_name, Symbol::new(&env, "invoice_refunded_event"));
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let fee_amount: i128 = data_map
+        .get(Symbol::new(&env, "fee_amount"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(fee_amount, 50);
+}
@@ -0,0 +1,287 @@
+#![cfg(test)]
+
+use crate::components::admin::{compute_fee, BPS_DENOMINATOR, FEE_CHANGE_DELAY};
+use crate::errors::ContractError;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::FeeRounding;
+use proptest::prelude::*;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test_with_fee(fee_bps: i128) -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let shade_contract_id = env.register(Shade, ());
+    let shade_client = ShadeClient::new(&env, &shade_contract_id);
+
+    let admin = Address::generate(&env);
+    shade_client.initialize(&admin);
+
+    let account_wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    shade_client.set_account_wasm_hash(&admin, &account_wasm_hash);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin.clone());
+
+    shade_client.add_accepted_token(&admin, &token.address());
+    shade_client.set_fee(&admin, &token.address(), &fee_bps);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    (env, shade_client, shade_contract_id, admin, token.address())
+}
+
+fn set_rounding(
+    env: &Env,
+    client: &ShadeClient,
+    admin: &Address,
+    token: &Address,
+    rounding: FeeRounding,
+) {
+    client.set_fee_rounding(admin, token, &rounding);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+}
+
+fn pay_new_invoice(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    token: &Address,
+    amount: i128,
+) -> (Address, u64) {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+
+    let merchant_account = client.deploy_merchant_account(&merchant);
+
+    let description = String::from_str(env, "Rounding");
+    let invoice_id = client.create_invoice(&merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &amount);
+    client.pay_invoice(&customer, &invoice_id);
+
+    (merchant_account, invoice_id)
+}
+
+#[test]
+fn test_compute_fee_rounding_modes() {
+    // 1005 * 5% = 50.25
+    assert_eq!(compute_fee(1005, 500, FeeRounding::Floor), Some(50));
+    assert_eq!(compute_fee(1005, 500, FeeRounding::Ceil), Some(51));
+    assert_eq!(compute_fee(1005, 500, FeeRounding::HalfEven), Some(50));
+
+    // 1010 * 5% = 50.5, ties go to the even neighbour
+    assert_eq!(compute_fee(1010, 500, FeeRounding::HalfEven), Some(50));
+    // 1030 * 5% = 51.5
+    assert_eq!(compute_fee(1030, 500, FeeRounding::HalfEven), Some(52));
+    // 1015 * 5% = 50.75
+    assert_eq!(compute_fee(1015, 500, FeeRounding::HalfEven), Some(51));
+
+    // Exact results are unaffected by the rounding mode
+    assert_eq!(compute_fee(1000, 500, FeeRounding::Ceil), Some(50));
+}
+
+#[test]
+fn test_compute_fee_overflow() {
+    assert_eq!(compute_fee(i128::MAX, 500, FeeRounding::Floor), None);
+}
+
+#[test]
+fn test_fee_rounding_defaults_to_floor() {
+    let (_env, client, _contract_id, _admin, token) = setup_test_with_fee(500);
+
+    assert_eq!(client.get_fee_rounding(&token), FeeRounding::Floor);
+}
+
+#[test]
+fn test_payment_with_ceil_rounding() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_fee(500);
+    set_rounding(&env, &client, &admin, &token, FeeRounding::Ceil);
+    assert_eq!(client.get_fee_rounding(&token), FeeRounding::Ceil);
+
+    let (merchant_account, invoice_id) = pay_new_invoice(&env, &client, &token, 1005);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 51);
+    assert_eq!(token_client.balance(&merchant_account), 954);
+    assert_eq!(client.get_invoice_fee(&invoice_id).fee_amount, 51);
+}
+
+#[test]
+fn test_payment_with_half_even_rounding() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_fee(500);
+    set_rounding(&env, &client, &admin, &token, FeeRounding::HalfEven);
+
+    let (merchant_account, _invoice_id) = pay_new_invoice(&env, &client, &token, 1030);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 52);
+    assert_eq!(token_client.balance(&merchant_account), 978);
+}
+
+#[test]
+fn test_rounding_increase_is_delayed() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_fee(500);
+    let now = env.ledger().timestamp();
+
+    client.set_fee_rounding(&admin, &token, &FeeRounding::Ceil);
+    assert_eq!(client.get_fee_rounding(&token), FeeRounding::Floor);
+    let pending = client.get_pending_fee_rounding(&token).unwrap();
+    assert_eq!(pending.rounding, FeeRounding::Ceil);
+    assert_eq!(pending.effective_at, now + FEE_CHANGE_DELAY);
+
+    // Fees keep the current rounding until the change takes effect
+    let (merchant_account, _invoice_id) = pay_new_invoice(&env, &client, &token, 1005);
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&merchant_account),
+        955
+    );
+
+    env.ledger().set_timestamp(now + FEE_CHANGE_DELAY);
+    assert_eq!(client.get_fee_rounding(&token), FeeRounding::Ceil);
+    assert_eq!(client.get_pending_fee_rounding(&token), None);
+}
+
+#[test]
+fn test_rounding_decrease_applies_immediately() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_fee(500);
+    set_rounding(&env, &client, &admin, &token, FeeRounding::HalfEven);
+
+    client.set_fee_rounding(&admin, &token, &FeeRounding::Ceil);
+    client.set_fee_rounding(&admin, &token, &FeeRounding::Floor);
+
+    assert_eq!(client.get_fee_rounding(&token), FeeRounding::Floor);
+    assert_eq!(client.get_pending_fee_rounding(&token), None);
+}
+
+#[test]
+fn test_set_fee_rounding_unauthorized() {
+    let (env, client, _contract_id, _admin, token) = setup_test_with_fee(500);
+
+    let non_admin = Address::generate(&env);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::NotAuthorized as u32);
+
+    let result = client.try_set_fee_rounding(&non_admin, &token, &FeeRounding::Ceil);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+}
+
+#[test]
+fn test_set_fee_rejects_out_of_range_bps() {
+    let (_env, client, _contract_id, admin, token) = setup_test_with_fee(500);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::InvalidAmount as u32);
+
+    let result = client.try_set_fee(&admin, &token, &(BPS_DENOMINATOR + 1));
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+
+    let result = client.try_set_fee(&admin, &token, &-1);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #21)")]
+fn test_payment_fee_overflow() {
+    let (env, client, _contract_id, _admin, token) = setup_test_with_fee(500);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    client.deploy_merchant_account(&merchant);
+
+    let description = String::from_str(&env, "Huge");
+    let invoice_id = client.create_invoice(&merchant, &description, &i128::MAX, &token);
+
+    let customer = Address::generate(&env);
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+fn rounding_strategy() -> impl Strategy<Value = FeeRounding> {
+    prop_oneof![
+        Just(FeeRounding::Floor),
+        Just(FeeRounding::Ceil),
+        Just(FeeRounding::HalfEven),
+    ]
+}
+
+/// Reference rounding done on unsigned integers by picking the nearest
+/// multiple of the denominator, independent of `compute_fee`.
+fn reference_fee(amount: i128, fee_bps: i128, rounding: FeeRounding) -> i128 {
+    let numerator = amount as u128 * fee_bps as u128;
+    let denominator = BPS_DENOMINATOR as u128;
+    let floor = numerator / denominator;
+
+    let fee = match rounding {
+        FeeRounding::Floor => floor,
+        FeeRounding::Ceil => numerator.div_ceil(denominator),
+        FeeRounding::HalfEven => {
+            let distance_down = numerator - floor * denominator;
+            let distance_up = (floor + 1) * denominator - numerator;
+            if distance_down < distance_up
+                || (distance_down == distance_up && floor.is_multiple_of(2))
+            {
+                floor
+            } else {
+                floor + 1
+            }
+        }
+    };
+    fee as i128
+}
+
+proptest! {
+    #[test]
+    fn prop_fee_matches_reference_rounding(
+        amount in 1..=i128::MAX / BPS_DENOMINATOR,
+        fee_bps in 0..=BPS_DENOMINATOR,
+        rounding in rounding_strategy(),
+    ) {
+        let fee = compute_fee(amount, fee_bps, rounding).unwrap();
+
+        prop_assert_eq!(fee, reference_fee(amount, fee_bps, rounding));
+        prop_assert!(fee >= 0);
+        prop_assert!(fee <= amount);
+    }
+
+    #[test]
+    fn prop_rounding_modes_are_ordered(
+        amount in 1..=i128::MAX / BPS_DENOMINATOR,
+        fee_bps in 0..=BPS_DENOMINATOR,
+    ) {
+        let floor = compute_fee(amount, fee_bps, FeeRounding::Floor).unwrap();
+        let half_even = compute_fee(amount, fee_bps, FeeRounding::HalfEven).unwrap();
+        let ceil = compute_fee(amount, fee_bps, FeeRounding::Ceil).unwrap();
+
+        prop_assert!(floor <= half_even);
+        prop_assert!(half_even <= ceil);
+        prop_assert!(ceil - floor <= 1);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn prop_payment_conserves_invoice_amount(
+        amount in 1..=1_000_000_000_000i128,
+        fee_bps in 0..=BPS_DENOMINATOR,
+        rounding in rounding_strategy(),
+    ) {
+        let (env, client, shade_contract_id, admin, token) = setup_test_with_fee(fee_bps);
+        set_rounding(&env, &client, &admin, &token, rounding);
+
+        let (merchant_account, invoice_id) = pay_new_invoice(&env, &client, &token, amount);
+
+        let token_client = token::TokenClient::new(&env, &token);
+        let fee = token_client.balance(&shade_contract_id);
+        let merchant_amount = token_client.balance(&merchant_account);
+
+        prop_assert_eq!(fee + merchant_amount, amount);
+        prop_assert_eq!(client.get_invoice_fee(&invoice_id).fee_amount, fee);
+    }
+}
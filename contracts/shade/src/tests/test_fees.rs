@@ -1,10 +1,12 @@
 #![cfg(test)]
 
 use crate::components::admin as admin_component;
+use crate::components::admin::FEE_CHANGE_DELAY;
 use crate::errors::ContractError;
 use crate::shade::Shade;
 use crate::shade::ShadeClient;
-use soroban_sdk::testutils::{Address as _, Events as _};
+use crate::types::PendingFee;
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
 use soroban_sdk::{Address, Env, Map, Symbol, TryIntoVal, Val};
 
 fn setup_with_accepted_token(env: &Env) -> (Address, ShadeClient<'_>, Address) {
@@ -34,7 +36,7 @@ fn assert_fee_set_event(
     expected_timestamp: u64,
 ) {
     let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!events.is_empty());
 
     let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
     assert_eq!(event_contract_id, contract_id.clone());
@@ -57,21 +59,162 @@ fn assert_fee_set_event(
     assert_eq!(timestamp_in_event, expected_timestamp);
 }
 
+fn advance_past_fee_delay(env: &Env) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+}
+
 #[test]
 fn test_set_fee_success() {
     let env = Env::default();
     let (admin, client, token) = setup_with_accepted_token(&env);
-    let contract_id = client.address.clone();
     let fee: i128 = 500;
 
+    client.set_fee(&admin, &token, &fee);
+    advance_past_fee_delay(&env);
+
+    assert_eq!(client.get_fee(&token), fee);
+}
+
+#[test]
+fn test_fee_decrease_applies_immediately() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+    let contract_id = client.address.clone();
+
+    client.set_fee(&admin, &token, &500);
+    advance_past_fee_delay(&env);
+
     let expected_timestamp = env.ledger().timestamp();
 
     env.as_contract(&contract_id, || {
-        admin_component::set_fee(&env, &admin, &token, fee);
-        assert_fee_set_event(&env, &contract_id, &token, fee, expected_timestamp);
+        admin_component::set_fee(&env, &admin, &token, 200);
+        assert_fee_set_event(&env, &contract_id, &token, 200, expected_timestamp);
     });
 
-    assert_eq!(client.get_fee(&token), fee);
+    assert_eq!(client.get_fee(&token), 200);
+    assert_eq!(client.get_pending_fee(&token), None);
+}
+
+#[test]
+fn test_fee_increase_is_scheduled() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+    let contract_id = client.address.clone();
+
+    env.ledger().set_timestamp(1_000);
+    let effective_at = 1_000 + FEE_CHANGE_DELAY;
+
+    env.as_contract(&contract_id, || {
+        admin_component::set_fee(&env, &admin, &token, 500);
+
+        let events = env.events().all();
+        let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+        assert_eq!(event_contract_id, contract_id.clone());
+
+        let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+        assert_eq!(event_name, Symbol::new(&env, "fee_change_scheduled_event"));
+
+        let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+        let current_fee: i128 = data_map
+            .get(Symbol::new(&env, "current_fee"))
+            .unwrap()
+            .try_into_val(&env)
+            .unwrap();
+        let new_fee: i128 = data_map
+            .get(Symbol::new(&env, "new_fee"))
+            .unwrap()
+            .try_into_val(&env)
+            .unwrap();
+        let event_effective_at: u64 = data_map
+            .get(Symbol::new(&env, "effective_at"))
+            .unwrap()
+            .try_into_val(&env)
+            .unwrap();
+        assert_eq!(current_fee, 0);
+        assert_eq!(new_fee, 500);
+        assert_eq!(event_effective_at, effective_at);
+    });
+
+    assert_eq!(client.get_fee(&token), 0);
+    assert_eq!(
+        client.get_pending_fee(&token),
+        Some(PendingFee {
+            fee: 500,
+            effective_at,
+        })
+    );
+
+    env.ledger().set_timestamp(effective_at - 1);
+    assert_eq!(client.get_fee(&token), 0);
+
+    env.ledger().set_timestamp(effective_at);
+    assert_eq!(client.get_fee(&token), 500);
+    assert_eq!(client.get_pending_fee(&token), None);
+}
+
+#[test]
+fn test_cancel_fee_change() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+
+    client.set_fee(&admin, &token, &200);
+    advance_past_fee_delay(&env);
+
+    client.set_fee(&admin, &token, &800);
+    client.cancel_fee_change(&admin, &token);
+
+    assert_eq!(client.get_pending_fee(&token), None);
+
+    advance_past_fee_delay(&env);
+    assert_eq!(client.get_fee(&token), 200);
+}
+
+#[test]
+fn test_cancel_fee_change_after_activation() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+
+    client.set_fee(&admin, &token, &300);
+    advance_past_fee_delay(&env);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::NoPendingFeeChange as u32);
+
+    let result = client.try_cancel_fee_change(&admin, &token);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+    assert_eq!(client.get_fee(&token), 300);
+}
+
+#[test]
+fn test_cancel_fee_change_unauthorized() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+
+    client.set_fee(&admin, &token, &300);
+
+    let non_admin = Address::generate(&env);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::NotAuthorized as u32);
+
+    let result = client.try_cancel_fee_change(&non_admin, &token);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+}
+
+#[test]
+fn test_new_increase_replaces_matured_pending_fee() {
+    let env = Env::default();
+    let (admin, client, token) = setup_with_accepted_token(&env);
+
+    client.set_fee(&admin, &token, &300);
+    advance_past_fee_delay(&env);
+
+    client.set_fee(&admin, &token, &600);
+    assert_eq!(client.get_fee(&token), 300);
+
+    advance_past_fee_delay(&env);
+    assert_eq!(client.get_fee(&token), 600);
 }
 
 #[test]
@@ -114,9 +257,11 @@ fn test_update_fee() {
     let (admin, client, token) = setup_with_accepted_token(&env);
 
     client.set_fee(&admin, &token, &200);
+    advance_past_fee_delay(&env);
     assert_eq!(client.get_fee(&token), 200);
 
     client.set_fee(&admin, &token, &750);
+    advance_past_fee_delay(&env);
     assert_eq!(client.get_fee(&token), 750);
 }
 
@@ -1,8 +1,10 @@
 #![cfg(test)]
 
+use crate::components::admin::FEE_CHANGE_DELAY;
 use crate::events::InvoiceRefundedEvent;
 use crate::shade::{Shade, ShadeClient};
-use crate::types::{DataKey, InvoiceStatus};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{DataKey, FeeBearer, InvoiceStatus};
 use account::account::{MerchantAccount, MerchantAccountClient};
 use soroban_sdk::events::Event;
 use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
@@ -27,7 +29,7 @@ fn assert_latest_invoice_event(
     expected_token: &Address,
 ) {
     let events = env.events().all();
-    assert!(events.len() > 0, "No events captured for invoice!");
+    assert!(!events.is_empty(), "No events captured for invoice!");
 
     let (event_contract_id, _topics, data) = events.get(events.len() - 1).unwrap();
     assert_eq!(&event_contract_id, contract_id);
@@ -59,7 +61,6 @@ fn create_test_token(env: &Env) -> Address {
 fn mark_invoice_paid(
     env: &Env,
     shade_contract_id: &Address,
-    merchant: &Address,
     invoice_id: u64,
     payer: &Address,
     date_paid: u64,
@@ -76,7 +77,7 @@ fn mark_invoice_paid(
             .persistent()
             .set(&DataKey::Invoice(invoice_id), &invoice);
         env.storage().persistent().set(
-            &DataKey::MerchantBalance(merchant.clone()),
+            &DataKey::MerchantAccount(invoice.merchant_id),
             merchant_account_id,
         );
     });
@@ -84,12 +85,13 @@ fn mark_invoice_paid(
 
 #[test]
 fn test_create_and_get_invoice_success() {
-    let (env, client, contract_id, _admin) = setup_test();
+    let (env, client, contract_id, admin) = setup_test();
 
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    let token = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let description = String::from_str(&env, "Test Invoice");
     let amount: i128 = 1000;
 
@@ -110,13 +112,15 @@ fn test_create_and_get_invoice_success() {
 
 #[test]
 fn test_create_multiple_invoices() {
-    let (env, client, _contract_id, _admin) = setup_test();
+    let (env, client, _contract_id, admin) = setup_test();
 
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    let token1 = Address::generate(&env);
-    let token2 = Address::generate(&env);
+    let token1 = create_test_token(&env);
+    client.add_accepted_token(&admin, &token1);
+    let token2 = create_test_token(&env);
+    client.add_accepted_token(&admin, &token2);
 
     let id1 = client.create_invoice(
         &merchant,
@@ -179,11 +183,12 @@ fn test_create_invoice_invalid_amount() {
 
 #[test]
 fn test_refund_invoice_success_within_window() {
-    let (env, client, shade_contract_id, _admin) = setup_test();
+    let (env, client, shade_contract_id, admin) = setup_test();
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
     let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let payer = Address::generate(&env);
     let description = String::from_str(&env, "Refundable Invoice");
     let amount = 1_000_i128;
@@ -200,7 +205,6 @@ fn test_refund_invoice_success_within_window() {
     mark_invoice_paid(
         &env,
         &shade_contract_id,
-        &merchant,
         invoice_id,
         &payer,
         900,
@@ -211,12 +215,14 @@ fn test_refund_invoice_success_within_window() {
     client.refund_invoice(&merchant, &invoice_id);
 
     let events = env.events().all();
-    assert!(events.len() >= 1);
+    assert!(!events.is_empty());
     let emitted = events.get(events.len() - 1).unwrap();
     let expected = InvoiceRefundedEvent {
         invoice_id,
         merchant: merchant.clone(),
         amount,
+        fee_amount: 0,
+        fee_bearer: FeeBearer::Merchant,
         timestamp: env.ledger().timestamp(),
     };
     let expected_data_val = expected.data(&env);
@@ -237,11 +243,12 @@ fn test_refund_invoice_success_within_window() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #15)")]
 fn test_refund_invoice_fails_after_refund_window() {
-    let (env, client, shade_contract_id, _admin) = setup_test();
+    let (env, client, shade_contract_id, admin) = setup_test();
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
     let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let payer = Address::generate(&env);
     let invoice_id = client.create_invoice(
         &merchant,
@@ -258,7 +265,6 @@ fn test_refund_invoice_fails_after_refund_window() {
     mark_invoice_paid(
         &env,
         &shade_contract_id,
-        &merchant,
         invoice_id,
         &payer,
         0,
@@ -273,12 +279,13 @@ fn test_refund_invoice_fails_after_refund_window() {
 
 #[test]
 fn test_void_invoice_success() {
-    let (env, client, _contract_id, _admin) = setup_test();
+    let (env, client, _contract_id, admin) = setup_test();
 
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    let token = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let description = String::from_str(&env, "Test Invoice");
     let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
 
@@ -297,13 +304,14 @@ fn test_void_invoice_success() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #1)")]
 fn test_refund_invoice_fails_for_non_owner() {
-    let (env, client, shade_contract_id, _admin) = setup_test();
+    let (env, client, shade_contract_id, admin) = setup_test();
     let merchant = Address::generate(&env);
     let other_merchant = Address::generate(&env);
     client.register_merchant(&merchant);
     client.register_merchant(&other_merchant);
 
     let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let payer = Address::generate(&env);
     let invoice_id = client.create_invoice(
         &merchant,
@@ -320,7 +328,6 @@ fn test_refund_invoice_fails_for_non_owner() {
     mark_invoice_paid(
         &env,
         &shade_contract_id,
-        &merchant,
         invoice_id,
         &payer,
         90,
@@ -334,12 +341,13 @@ fn test_refund_invoice_fails_for_non_owner() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #1)")]
 fn test_void_invoice_non_owner() {
-    let (env, client, _contract_id, _admin) = setup_test();
+    let (env, client, _contract_id, admin) = setup_test();
 
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    let token = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let description = String::from_str(&env, "Test Invoice");
     let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
 
@@ -352,15 +360,14 @@ fn test_void_invoice_non_owner() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #14)")]
 fn test_void_invoice_already_paid() {
-    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (env, client, _contract_id, _admin, token) = setup_test_with_payment();
 
     // Register merchant
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    client.deploy_merchant_account(&merchant);
 
     // Create and pay invoice
     let description = String::from_str(&env, "Test Invoice");
@@ -379,12 +386,13 @@ fn test_void_invoice_already_paid() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #14)")]
 fn test_void_invoice_already_cancelled() {
-    let (env, client, _contract_id, _admin) = setup_test();
+    let (env, client, _contract_id, admin) = setup_test();
 
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    let token = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.add_accepted_token(&admin, &token);
     let description = String::from_str(&env, "Test Invoice");
     let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
 
@@ -398,15 +406,14 @@ fn test_void_invoice_already_cancelled() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #14)")]
 fn test_pay_cancelled_invoice() {
-    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (env, client, _contract_id, _admin, token) = setup_test_with_payment();
 
     // Register merchant
     let merchant = Address::generate(&env);
     client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    client.deploy_merchant_account(&merchant);
 
     // Create invoice
     let description = String::from_str(&env, "Test Invoice");
@@ -445,11 +452,16 @@ fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Ad
     let admin = Address::generate(&env);
     shade_client.initialize(&admin);
 
+    let account_wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    shade_client.set_account_wasm_hash(&admin, &account_wasm_hash);
+
     let token_admin = Address::generate(&env);
     let token = env.register_stellar_asset_contract_v2(token_admin.clone());
 
     shade_client.add_accepted_token(&admin, &token.address());
     shade_client.set_fee(&admin, &token.address(), &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
 
     (env, shade_client, shade_contract_id, admin, token.address())
 }
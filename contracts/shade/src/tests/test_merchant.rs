@@ -23,7 +23,7 @@ fn assert_latest_merchant_status_event(
     expected_timestamp: u64,
 ) {
     let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!events.is_empty());
 
     let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
     assert_eq!(&event_contract_id, contract_id);
@@ -64,7 +64,7 @@ fn test_set_merchant_status_admin_can_deactivate() {
     client.register_merchant(&merchant);
 
     // Merchant should be active by default
-    assert_eq!(client.is_merchant_active(&1), true);
+    assert!(client.is_merchant_active(&1));
 
     let expected_timestamp = env.ledger().timestamp();
 
@@ -73,7 +73,7 @@ fn test_set_merchant_status_admin_can_deactivate() {
         assert_latest_merchant_status_event(&env, &contract_id, 1, false, expected_timestamp);
     });
 
-    assert_eq!(client.is_merchant_active(&1), false);
+    assert!(!client.is_merchant_active(&1));
 }
 
 #[test]
@@ -94,7 +94,7 @@ fn test_set_merchant_status_admin_can_activate() {
     env.as_contract(&contract_id, || {
         merchant_component::set_merchant_status(&env, &admin, 1, false);
     });
-    assert_eq!(client.is_merchant_active(&1), false);
+    assert!(!client.is_merchant_active(&1));
 
     // Now activate
     let expected_timestamp = env.ledger().timestamp();
@@ -104,7 +104,7 @@ fn test_set_merchant_status_admin_can_activate() {
         assert_latest_merchant_status_event(&env, &contract_id, 1, true, expected_timestamp);
     });
 
-    assert_eq!(client.is_merchant_active(&1), true);
+    assert!(client.is_merchant_active(&1));
 }
 
 #[should_panic(expected = "HostError: Error(Contract, #1)")]
@@ -163,7 +163,7 @@ fn test_merchant_active_by_default() {
     client.register_merchant(&merchant);
 
     // Newly registered merchant should be active
-    assert_eq!(client.is_merchant_active(&1), true);
+    assert!(client.is_merchant_active(&1));
 }
 
 #[test]
@@ -177,20 +177,20 @@ fn test_multiple_merchants_independent_status() {
     client.register_merchant(&merchant2);
 
     // Both should be active
-    assert_eq!(client.is_merchant_active(&1), true);
-    assert_eq!(client.is_merchant_active(&2), true);
+    assert!(client.is_merchant_active(&1));
+    assert!(client.is_merchant_active(&2));
 
     // Deactivate merchant 1
     client.set_merchant_status(&admin, &1, &false);
 
     // Check they have independent status
-    assert_eq!(client.is_merchant_active(&1), false);
-    assert_eq!(client.is_merchant_active(&2), true);
+    assert!(!client.is_merchant_active(&1));
+    assert!(client.is_merchant_active(&2));
 
     // Reactivate merchant 1, merchant 2 should remain active
     client.set_merchant_status(&admin, &1, &true);
-    assert_eq!(client.is_merchant_active(&1), true);
-    assert_eq!(client.is_merchant_active(&2), true);
+    assert!(client.is_merchant_active(&1));
+    assert!(client.is_merchant_active(&2));
 }
 
 #[test]
@@ -0,0 +1,346 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::Role;
+use account::account::{MerchantAccount, MerchantAccountClient};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{token, Address, Env, Map, String, Symbol, TryIntoVal, Val};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    (env, client, contract_id, admin)
+}
+
+fn upload_account_wasm(env: &Env, client: &ShadeClient<'_>, admin: &Address) {
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(admin, &wasm_hash);
+}
+
+#[test]
+fn test_deploy_merchant_account_success() {
+    let (env, client, contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let account = client.deploy_merchant_account(&merchant);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(
+        event_name,
+        Symbol::new(&env, "merchant_account_deployed_event")
+    );
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let merchant_in_event: Address = data_map
+        .get(Symbol::new(&env, "merchant"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let contract_in_event: Address = data_map
+        .get(Symbol::new(&env, "contract"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(merchant_in_event, merchant);
+    assert_eq!(contract_in_event, account);
+
+    assert_eq!(client.get_merchant_account(&1), account);
+
+    let account_client = MerchantAccountClient::new(&env, &account);
+    assert_eq!(account_client.get_merchant(), merchant);
+}
+
+#[test]
+fn test_payment_to_deployed_merchant_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let token_admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    let account = client.deploy_merchant_account(&merchant);
+
+    let description = String::from_str(&env, "Deployed account");
+    let invoice_id = client.create_invoice(&merchant, &description, &1000, &token);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1000);
+    client.pay_invoice(&customer, &invoice_id);
+
+    let account_client = MerchantAccountClient::new(&env, &account);
+    assert_eq!(account_client.get_balance(&token), 1000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_deploy_merchant_account_without_wasm_hash() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    client.deploy_merchant_account(&merchant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #23)")]
+fn test_deploy_merchant_account_twice() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    client.deploy_merchant_account(&merchant);
+    client.deploy_merchant_account(&merchant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_deploy_merchant_account_unregistered() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let stranger = Address::generate(&env);
+    client.deploy_merchant_account(&stranger);
+}
+
+#[test]
+fn test_set_merchant_account_accepts_deployed_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    let account = client.deploy_merchant_account(&merchant);
+
+    client.set_merchant_account(&merchant, &account);
+    assert_eq!(client.get_merchant_account(&1), account);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_set_merchant_account_rejects_arbitrary_address() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let attacker_wallet = Address::generate(&env);
+    client.set_merchant_account(&merchant, &attacker_wallet);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_set_merchant_account_rejects_other_merchants_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    let other_merchant = Address::generate(&env);
+    client.register_merchant(&other_merchant);
+
+    let other_account = client.deploy_merchant_account(&other_merchant);
+    client.set_merchant_account(&merchant, &other_account);
+}
+
+#[test]
+fn test_approve_merchant_account() {
+    let (env, client, contract_id, admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &account).initialize(&merchant, &contract_id, &1_u64);
+
+    client.approve_merchant_account(&admin, &1, &account);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, _data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(
+        event_name,
+        Symbol::new(&env, "merchant_account_approved_event")
+    );
+
+    assert_eq!(client.get_merchant_account(&1), account);
+
+    // Once approved the merchant may point back at it later
+    client.set_merchant_account(&merchant, &account);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_approve_merchant_account_wrong_manager() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let foreign_manager = Address::generate(&env);
+    let account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &account).initialize(&merchant, &foreign_manager, &1_u64);
+
+    client.approve_merchant_account(&admin, &1, &account);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_approve_merchant_account_wrong_merchant() {
+    let (env, client, contract_id, admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let someone_else = Address::generate(&env);
+    let account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &account).initialize(&someone_else, &contract_id, &1_u64);
+
+    client.approve_merchant_account(&admin, &1, &account);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_approve_merchant_account_non_admin() {
+    let (env, client, contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &account).initialize(&merchant, &contract_id, &1_u64);
+
+    let non_admin = Address::generate(&env);
+    client.approve_merchant_account(&non_admin, &1, &account);
+}
+
+fn setup_deployed_account(env: &Env, client: &ShadeClient<'_>, admin: &Address) -> Address {
+    upload_account_wasm(env, client, admin);
+
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant)
+}
+
+#[test]
+fn test_verify_merchant_propagates_to_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    let account = setup_deployed_account(&env, &client, &admin);
+    let account_client = MerchantAccountClient::new(&env, &account);
+
+    assert!(!account_client.is_verified_account());
+    client.verify_merchant(&admin, &1, &true);
+    assert!(account_client.is_verified_account());
+}
+
+#[test]
+fn test_set_merchant_status_propagates_to_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    let account = setup_deployed_account(&env, &client, &admin);
+    let account_client = MerchantAccountClient::new(&env, &account);
+
+    client.set_merchant_status(&admin, &1, &false);
+    assert!(account_client.is_restricted_account());
+
+    client.set_merchant_status(&admin, &1, &true);
+    assert!(!account_client.is_restricted_account());
+}
+
+#[test]
+fn test_deployed_account_inherits_merchant_status() {
+    let (env, client, _contract_id, admin) = setup_test();
+    upload_account_wasm(&env, &client, &admin);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.verify_merchant(&admin, &1, &true);
+    client.set_merchant_status(&admin, &1, &false);
+
+    let account = client.deploy_merchant_account(&merchant);
+    let account_client = MerchantAccountClient::new(&env, &account);
+    assert!(account_client.is_verified_account());
+    assert!(account_client.is_restricted_account());
+}
+
+#[test]
+fn test_operator_manages_merchant_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+    let account = setup_deployed_account(&env, &client, &admin);
+    let account_client = MerchantAccountClient::new(&env, &account);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Operator);
+
+    client.add_merchant_account_token(&operator, &1, &token);
+    assert!(account_client.has_token(&token));
+
+    client.verify_merchant_account(&operator, &1);
+    assert!(account_client.is_verified_account());
+
+    client.restrict_merchant_account(&operator, &1, &true);
+    assert!(account_client.is_restricted_account());
+    client.restrict_merchant_account(&admin, &1, &false);
+    assert!(!account_client.is_restricted_account());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_manage_merchant_account_non_operator() {
+    let (env, client, _contract_id, admin) = setup_test();
+    setup_deployed_account(&env, &client, &admin);
+
+    let stranger = Address::generate(&env);
+    client.restrict_merchant_account(&stranger, &1, &true);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_add_merchant_account_token_not_accepted() {
+    let (env, client, _contract_id, admin) = setup_test();
+    setup_deployed_account(&env, &client, &admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_merchant_account_token(&admin, &1, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
+fn test_verify_merchant_account_without_account() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    client.verify_merchant_account(&admin, &1);
+}
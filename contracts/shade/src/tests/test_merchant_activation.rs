@@ -4,8 +4,10 @@ use crate::components::merchant as merchant_component;
 use crate::errors::ContractError;
 use crate::shade::Shade;
 use crate::shade::ShadeClient;
+use crate::types::InvoiceStatus;
+use account::account::{MerchantAccount, MerchantAccountClient};
 use soroban_sdk::testutils::{Address as _, Events as _};
-use soroban_sdk::{Address, Env, Map, Symbol, TryIntoVal, Val};
+use soroban_sdk::{token, Address, Env, Map, String, Symbol, TryIntoVal, Val};
 
 fn assert_latest_merchant_status_event(
     env: &Env,
@@ -15,7 +17,7 @@ fn assert_latest_merchant_status_event(
     expected_timestamp: u64,
 ) {
     let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!events.is_empty());
 
     let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
     assert_eq!(event_contract_id, contract_id.clone());
@@ -206,3 +208,151 @@ fn test_is_merchant_active_zero_id() {
     let zero_merchant_id = 0u64;
     client.is_merchant_active(&zero_merchant_id);
 }
+
+#[test]
+fn test_create_invoice_inactive_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.set_merchant_status(&admin, &1, &false);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::MerchantInactive as u32);
+
+    let description = String::from_str(&env, "Inactive");
+    let result = client.try_create_invoice(&merchant, &description, &100, &token);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+}
+
+#[test]
+fn test_pay_invoice_inactive_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let description = String::from_str(&env, "Pending");
+    let invoice_id = client.create_invoice(&merchant, &description, &100, &token);
+
+    client.set_merchant_status(&admin, &1, &false);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &100);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::MerchantInactive as u32);
+
+    let result = client.try_pay_invoice(&customer, &invoice_id);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+
+    // The invoice stays pending and can still be voided by the merchant
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Pending
+    );
+    client.void_invoice(&merchant, &invoice_id);
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_pending_invoice_payable_after_reactivation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let merchant_account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &merchant_account).initialize(&merchant, &contract_id, &1);
+    client.approve_merchant_account(&admin, &1, &merchant_account);
+
+    let description = String::from_str(&env, "Pending");
+    let invoice_id = client.create_invoice(&merchant, &description, &100, &token);
+
+    client.set_merchant_status(&admin, &1, &false);
+    client.set_merchant_status(&admin, &1, &true);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &100);
+    client.pay_invoice(&customer, &invoice_id);
+
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+}
+
+#[test]
+fn test_refund_inactive_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let merchant_account = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &merchant_account).initialize(&merchant, &contract_id, &1);
+    client.approve_merchant_account(&admin, &1, &merchant_account);
+
+    let description = String::from_str(&env, "Paid");
+    let invoice_id = client.create_invoice(&merchant, &description, &100, &token);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &100);
+    client.pay_invoice(&customer, &invoice_id);
+
+    client.set_merchant_status(&admin, &1, &false);
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::MerchantInactive as u32);
+
+    let result = client.try_refund_invoice(&merchant, &invoice_id);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use account::account::MerchantAccountClient;
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Symbol, TryIntoVal, Val};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    (env, client, contract_id, admin)
+}
+
+#[test]
+fn test_change_merchant_address() {
+    let (env, client, contract_id, _admin) = setup_test();
+
+    let old_address = Address::generate(&env);
+    client.register_merchant(&old_address);
+    let key = BytesN::from_array(&env, &[3u8; 32]);
+    client.set_merchant_key(&old_address, &key);
+    let account = client.deploy_merchant_account(&old_address);
+
+    let new_address = Address::generate(&env);
+    client.change_merchant_address(&old_address, &new_address);
+
+    // Nothing moves until the new address accepts
+    assert_eq!(client.get_merchant(&1).address, old_address);
+
+    client.accept_merchant_address(&new_address, &1);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(
+        event_name,
+        Symbol::new(&env, "merchant_address_changed_event")
+    );
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let old_in_event: Address = data_map
+        .get(Symbol::new(&env, "old_address"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let new_in_event: Address = data_map
+        .get(Symbol::new(&env, "new_address"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(old_in_event, old_address);
+    assert_eq!(new_in_event, new_address);
+
+    assert_eq!(client.get_merchant(&1).address, new_address);
+    assert!(!client.is_merchant(&old_address));
+    assert!(client.is_merchant(&new_address));
+    assert_eq!(client.get_merchant_key(&new_address), key);
+    assert_eq!(
+        MerchantAccountClient::new(&env, &account).get_merchant(),
+        new_address
+    );
+}
+
+#[test]
+fn test_new_address_can_invoice_after_rotation() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let old_address = Address::generate(&env);
+    client.register_merchant(&old_address);
+
+    let new_address = Address::generate(&env);
+    client.change_merchant_address(&old_address, &new_address);
+    client.accept_merchant_address(&new_address, &1);
+
+    let description = String::from_str(&env, "Rotated");
+    let invoice_id = client.create_invoice(&new_address, &description, &100, &token);
+    assert_eq!(client.get_invoice(&invoice_id).merchant_id, 1);
+
+    let result = client.try_create_invoice(&old_address, &description, &100, &token);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_admin_recovers_merchant_address() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let lost_address = Address::generate(&env);
+    client.register_merchant(&lost_address);
+
+    let new_address = Address::generate(&env);
+    client.recover_merchant_address(&admin, &1, &new_address);
+    client.accept_merchant_address(&new_address, &1);
+
+    assert_eq!(client.get_merchant(&1).address, new_address);
+    assert!(!client.is_merchant(&lost_address));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_recover_merchant_address_non_admin() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let attacker = Address::generate(&env);
+    client.recover_merchant_address(&attacker, &1, &attacker);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #26)")]
+fn test_accept_merchant_address_without_proposal() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let new_address = Address::generate(&env);
+    client.accept_merchant_address(&new_address, &1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #26)")]
+fn test_accept_merchant_address_wrong_address() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let new_address = Address::generate(&env);
+    client.change_merchant_address(&merchant, &new_address);
+
+    let someone_else = Address::generate(&env);
+    client.accept_merchant_address(&someone_else, &1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_change_merchant_address_to_registered_merchant() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    let other_merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.register_merchant(&other_merchant);
+
+    client.change_merchant_address(&merchant, &other_merchant);
+}
@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::types::{MerchantProfile, Role};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Symbol, TryIntoVal, Val};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_registration_approval(&admin, &true);
+
+    (env, client, contract_id, admin)
+}
+
+fn profile(env: &Env, name: &str) -> MerchantProfile {
+    MerchantProfile {
+        name: String::from_str(env, name),
+        website: String::from_str(env, ""),
+        support_email_hash: BytesN::from_array(env, &[1u8; 32]),
+        logo_uri: String::from_str(env, ""),
+        category: String::from_str(env, "retail"),
+        country_code: String::from_str(env, "NG"),
+    }
+}
+
+#[test]
+fn test_registration_open_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    assert!(!client.requires_registration_approval());
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    assert!(client.is_merchant(&merchant));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_register_merchant_requires_approval() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+}
+
+#[test]
+fn test_operator_approves_application() {
+    let (env, client, contract_id, admin) = setup_test();
+
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Operator);
+
+    let applicant = Address::generate(&env);
+    client.submit_merchant_application(&applicant, &profile(&env, "Shop"));
+    assert!(!client.is_merchant(&applicant));
+
+    let pending = client.get_pending_merchants(&0, &10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().applicant, applicant);
+
+    let merchant_id = client.approve_merchant_application(&operator, &applicant);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(event_name, Symbol::new(&env, "application_approved_event"));
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let reviewer: Address = data_map
+        .get(Symbol::new(&env, "reviewer"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(reviewer, operator);
+
+    assert_eq!(merchant_id, 1);
+    assert!(client.is_merchant(&applicant));
+    assert!(client.get_merchant(&1).active);
+    assert_eq!(client.get_merchant_profile(&1), Some(profile(&env, "Shop")));
+    assert_eq!(client.get_pending_merchants(&0, &10).len(), 0);
+    assert_eq!(client.get_merchant_application(&applicant), None);
+}
+
+#[test]
+fn test_reject_application() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.submit_merchant_application(&first, &profile(&env, "First"));
+    client.submit_merchant_application(&second, &profile(&env, "Second"));
+
+    client.reject_merchant_application(&admin, &first);
+
+    let pending = client.get_pending_merchants(&0, &10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().applicant, second);
+    assert!(!client.is_merchant(&first));
+
+    // A rejected applicant may apply again
+    client.submit_merchant_application(&first, &profile(&env, "First again"));
+    assert_eq!(client.get_pending_merchants(&0, &10).len(), 2);
+}
+
+#[test]
+fn test_pending_applications_are_paginated() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.submit_merchant_application(&first, &profile(&env, "First"));
+    client.submit_merchant_application(&second, &profile(&env, "Second"));
+    client.submit_merchant_application(&third, &profile(&env, "Third"));
+    assert_eq!(client.get_pending_merchant_count(), 3);
+
+    let page = client.get_pending_merchants(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().applicant, second);
+    assert_eq!(client.get_pending_merchants(&2, &10).len(), 1);
+    assert_eq!(client.get_pending_merchants(&3, &10).len(), 0);
+
+    // Deciding an application moves the last one into its position
+    client.reject_merchant_application(&admin, &first);
+    assert_eq!(client.get_pending_merchant_count(), 2);
+    let pending = client.get_pending_merchants(&0, &10);
+    assert_eq!(pending.get(0).unwrap().applicant, third);
+    assert_eq!(pending.get(1).unwrap().applicant, second);
+}
+
+#[test]
+fn test_resubmission_replaces_pending_application() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let applicant = Address::generate(&env);
+    client.submit_merchant_application(&applicant, &profile(&env, "Draft"));
+    client.submit_merchant_application(&applicant, &profile(&env, "Final"));
+
+    let pending = client.get_pending_merchants(&0, &10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().profile, profile(&env, "Final"));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_approve_application_non_operator() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let applicant = Address::generate(&env);
+    client.submit_merchant_application(&applicant, &profile(&env, "Shop"));
+
+    client.approve_merchant_application(&applicant, &applicant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #32)")]
+fn test_approve_missing_application() {
+    let (env, client, _contract_id, admin) = setup_test();
+
+    let applicant = Address::generate(&env);
+    client.approve_merchant_application(&admin, &applicant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_submit_application_invalid_profile() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let applicant = Address::generate(&env);
+    client.submit_merchant_application(&applicant, &profile(&env, ""));
+}
@@ -0,0 +1,317 @@
+#![cfg(test)]
+
+use crate::components::invoice::MAX_REFUND_DURATION;
+use crate::errors::ContractError;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{DataKey, InvoiceStatus, MerchantDataKey};
+use account::account::MerchantAccountClient;
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String, Symbol, TryIntoVal};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    (env, client, contract_id, admin, token)
+}
+
+fn pay_new_invoice(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> u64 {
+    let description = String::from_str(env, "Closure");
+    let invoice_id = client.create_invoice(merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &amount);
+    client.pay_invoice(&customer, &invoice_id);
+
+    invoice_id
+}
+
+#[test]
+fn test_close_merchant() {
+    let (env, client, contract_id, _admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    let account = client.deploy_merchant_account(&merchant);
+
+    pay_new_invoice(&env, &client, &merchant, &token, 1000);
+    let description = String::from_str(&env, "Never paid");
+    let pending_id = client.create_invoice(&merchant, &description, &500, &token);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+
+    client.close_merchant(&merchant);
+
+    let events = env.events().all();
+    let (event_contract_id, topics, _data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(event_name, Symbol::new(&env, "merchant_closed_event"));
+
+    assert_eq!(
+        client.get_invoice(&pending_id).status,
+        InvoiceStatus::Cancelled
+    );
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&account), 0);
+    assert_eq!(token_client.balance(&merchant), 1000);
+    assert!(MerchantAccountClient::new(&env, &account).is_restricted_account());
+
+    assert!(client.is_merchant_closed(&1));
+    assert!(!client.get_merchant(&1).active);
+    assert!(!client.is_merchant(&merchant));
+}
+
+#[test]
+fn test_closed_merchant_address_can_register_again() {
+    let (env, client, _contract_id, _admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.close_merchant(&merchant);
+
+    client.register_merchant(&merchant);
+    assert!(client.is_merchant(&merchant));
+
+    assert_eq!(client.get_merchant(&2).address, merchant);
+    assert!(!client.is_merchant_closed(&2));
+    assert!(client.is_merchant_closed(&1));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #27)")]
+fn test_close_merchant_within_refund_window() {
+    let (env, client, _contract_id, _admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    pay_new_invoice(&env, &client, &merchant, &token, 1000);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION);
+
+    client.close_merchant(&merchant);
+}
+
+#[test]
+fn test_close_merchant_voids_only_pending_invoices() {
+    let (env, client, _contract_id, _admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    let description = String::from_str(&env, "Pending");
+    let voided_id = client.create_invoice(&merchant, &description, &100, &token);
+    let pending_id = client.create_invoice(&merchant, &description, &200, &token);
+    let paid_id = pay_new_invoice(&env, &client, &merchant, &token, 300);
+    client.void_invoice(&merchant, &voided_id);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+    client.close_merchant(&merchant);
+
+    assert_eq!(
+        client.get_invoice(&pending_id).status,
+        InvoiceStatus::Cancelled
+    );
+    assert_eq!(client.get_invoice(&paid_id).status, InvoiceStatus::Paid);
+
+    let stats = client.get_merchant_stats(&1);
+    assert_eq!(stats.pending_count, 0);
+    assert_eq!(stats.cancelled_count, 2);
+    assert_eq!(stats.paid_count, 1);
+}
+
+#[test]
+fn test_close_deactivated_merchant_sweeps_account() {
+    let (env, client, _contract_id, admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    let account = client.deploy_merchant_account(&merchant);
+    pay_new_invoice(&env, &client, &merchant, &token, 1000);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+    client.set_merchant_status(&admin, &1, &false);
+    client.close_merchant(&merchant);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&account), 0);
+    assert_eq!(token_client.balance(&merchant), 1000);
+    assert!(MerchantAccountClient::new(&env, &account).is_restricted_account());
+}
+
+#[test]
+fn test_close_merchant_skips_unmanaged_account() {
+    let (env, client, contract_id, _admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    // A legacy plain-address account is not something Shade can sweep
+    let legacy_account = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantAccount(1), &legacy_account);
+    });
+
+    client.close_merchant(&merchant);
+    assert!(client.is_merchant_closed(&1));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #28)")]
+fn test_closed_merchant_cannot_be_reactivated() {
+    let (env, client, _contract_id, admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.close_merchant(&merchant);
+
+    client.set_merchant_status(&admin, &1, &true);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_close_unregistered_merchant() {
+    let (env, client, _contract_id, _admin, _token) = setup_test();
+
+    let stranger = Address::generate(&env);
+    client.close_merchant(&stranger);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #28)")]
+fn test_closed_merchant_cannot_be_recovered() {
+    let (env, client, _contract_id, admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.close_merchant(&merchant);
+
+    client.recover_merchant_address(&admin, &1, &Address::generate(&env));
+}
+
+#[test]
+fn test_closed_merchant_address_change_cannot_be_accepted() {
+    let (env, client, contract_id, _admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.close_merchant(&merchant);
+
+    // A proposal left behind for the closed id
+    let new_address = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingMerchantAddress(1), &new_address);
+    });
+
+    let expected_error =
+        soroban_sdk::Error::from_contract_error(ContractError::MerchantClosed as u32);
+    let result = client.try_accept_merchant_address(&new_address, &1);
+    assert!(matches!(result, Err(Ok(err)) if err == expected_error));
+    assert!(!client.is_merchant(&new_address));
+}
+
+// Turns merchant 1 into one registered before invoices were tracked
+fn make_legacy(env: &Env, contract_id: &Address) {
+    env.as_contract(contract_id, || {
+        let storage = env.storage().persistent();
+        storage.remove(&MerchantDataKey::InvoicesIndexed(1));
+        storage.remove(&MerchantDataKey::PendingInvoices(1));
+        storage.remove(&DataKey::MerchantStats(1));
+    });
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #49)")]
+fn test_close_legacy_merchant_requires_migration() {
+    let (env, client, contract_id, _admin, _token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    make_legacy(&env, &contract_id);
+
+    client.close_merchant(&merchant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #27)")]
+fn test_migrated_legacy_payment_blocks_closure() {
+    let (env, client, contract_id, admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+    pay_new_invoice(&env, &client, &merchant, &token, 1000);
+    make_legacy(&env, &contract_id);
+
+    assert!(client.migrate_merchant_invoices(&admin, &1, &10));
+    assert!(client.get_merchant_stats(&1).last_payment_at.is_some());
+
+    client.close_merchant(&merchant);
+}
+
+#[test]
+fn test_close_migrated_legacy_merchant() {
+    let (env, client, contract_id, admin, token) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    let other = Address::generate(&env);
+    client.register_merchant(&other);
+
+    let description = String::from_str(&env, "Legacy");
+    let first_id = client.create_invoice(&merchant, &description, &100, &token);
+    client.create_invoice(&other, &description, &100, &token);
+    let second_id = client.create_invoice(&merchant, &description, &200, &token);
+    make_legacy(&env, &contract_id);
+
+    // Migrated over several calls
+    assert!(!client.migrate_merchant_invoices(&admin, &1, &2));
+    assert!(client.migrate_merchant_invoices(&admin, &1, &2));
+    assert!(client.migrate_merchant_invoices(&admin, &1, &2));
+
+    client.close_merchant(&merchant);
+
+    assert_eq!(
+        client.get_invoice(&first_id).status,
+        InvoiceStatus::Cancelled
+    );
+    assert_eq!(
+        client.get_invoice(&second_id).status,
+        InvoiceStatus::Cancelled
+    );
+    assert_eq!(client.get_invoice(&2).status, InvoiceStatus::Pending);
+}
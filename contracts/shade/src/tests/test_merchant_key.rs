@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use crate::shade::{Shade, ShadeClient};
-use soroban_sdk::testutils::{Address as _, Events as _};
+use crate::types::DataKey;
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
 use soroban_sdk::{Address, BytesN, Env, Map, Symbol, TryIntoVal, Val};
 
 fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
@@ -77,3 +78,151 @@ fn test_get_non_existent_key() {
 
     client.get_merchant_key(&merchant);
 }
+
+#[test]
+fn test_rotation_keeps_history() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let key1 = BytesN::from_array(&env, &[1u8; 32]);
+    let key2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    env.ledger().set_timestamp(100);
+    client.set_merchant_key(&merchant, &key1);
+    env.ledger().set_timestamp(200);
+    client.set_merchant_key(&merchant, &key2);
+
+    let keys = client.get_merchant_keys(&1);
+    assert_eq!(keys.len(), 2);
+
+    let first = keys.get(0).unwrap();
+    assert_eq!(first.key, key1);
+    assert_eq!(first.version, 1);
+    assert_eq!(first.activated_at, 100);
+    assert_eq!(first.revoked_at, Some(200));
+
+    let second = keys.get(1).unwrap();
+    assert_eq!(second.key, key2);
+    assert_eq!(second.version, 2);
+    assert_eq!(second.activated_at, 200);
+    assert_eq!(second.revoked_at, None);
+
+    assert_eq!(client.get_merchant_key_at(&1, &100), key1);
+    assert_eq!(client.get_merchant_key_at(&1, &199), key1);
+    assert_eq!(client.get_merchant_key_at(&1, &200), key2);
+    assert_eq!(client.get_merchant_key_at(&1, &5000), key2);
+}
+
+#[test]
+fn test_rotation_emits_rotated_event() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let key1 = BytesN::from_array(&env, &[1u8; 32]);
+    let key2 = BytesN::from_array(&env, &[2u8; 32]);
+    client.set_merchant_key(&merchant, &key1);
+    client.set_merchant_key(&merchant, &key2);
+
+    let events = env.events().all();
+    let (_, topics, data) = events.get(events.len() - 1).unwrap();
+    let name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(name, Symbol::new(&env, "merchant_key_rotated_event"));
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let old_key: BytesN<32> = data_map
+        .get(Symbol::new(&env, "old_key"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    let version: u32 = data_map
+        .get(Symbol::new(&env, "version"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(old_key, key1);
+    assert_eq!(version, 2);
+}
+
+#[test]
+fn test_revoke_merchant_key() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let key = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().set_timestamp(100);
+    client.set_merchant_key(&merchant, &key);
+    env.ledger().set_timestamp(300);
+    client.revoke_merchant_key(&merchant);
+
+    let events = env.events().all();
+    let (_, topics, _) = events.get(events.len() - 1).unwrap();
+    let name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(name, Symbol::new(&env, "merchant_key_revoked_event"));
+
+    let record = client.get_merchant_keys(&1).get(0).unwrap();
+    assert_eq!(record.revoked_at, Some(300));
+    assert_eq!(client.get_merchant_key_at(&1, &250), key);
+    assert!(client.try_get_merchant_key(&merchant).is_err());
+    assert!(client.try_get_merchant_key_at(&1, &300).is_err());
+
+    let new_key = BytesN::from_array(&env, &[2u8; 32]);
+    client.set_merchant_key(&merchant, &new_key);
+    assert_eq!(client.get_merchant_key(&merchant), new_key);
+    assert_eq!(client.get_merchant_keys(&1).get(1).unwrap().version, 2);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_revoke_without_active_key() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    client.revoke_merchant_key(&merchant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_key_at_before_first_activation() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    env.ledger().set_timestamp(100);
+    client.set_merchant_key(&merchant, &BytesN::from_array(&env, &[1u8; 32]));
+
+    client.get_merchant_key_at(&1, &99);
+}
+
+#[test]
+fn test_close_merchant_revokes_legacy_key() {
+    let (env, client, contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    // A key stored before key history existed
+    let key = BytesN::from_array(&env, &[1u8; 32]);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantKey(merchant.clone()), &key);
+    });
+
+    env.ledger().set_timestamp(500);
+    client.close_merchant(&merchant);
+
+    let history = client.get_merchant_keys(&1);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.key, key);
+    assert_eq!(record.revoked_at, Some(500));
+}
@@ -0,0 +1,148 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::MerchantFilter;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{Address, BytesN, Env};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    (env, client, admin)
+}
+
+fn empty_filter() -> MerchantFilter {
+    MerchantFilter {
+        is_active: None,
+        is_verified: None,
+        category: None,
+        country_code: None,
+        registered_from: None,
+        registered_until: None,
+        has_account: None,
+        has_key: None,
+    }
+}
+
+fn register_at(env: &Env, client: &ShadeClient, timestamp: u64) -> Address {
+    env.ledger().set_timestamp(timestamp);
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+    merchant
+}
+
+#[test]
+fn test_get_merchant_by_address() {
+    let (env, client, _admin) = setup_test();
+    register_at(&env, &client, 100);
+    let merchant = register_at(&env, &client, 200);
+
+    assert_eq!(client.get_merchant_id(&merchant), 2);
+    assert_eq!(client.get_merchant_count(), 2);
+
+    let merchant_data = client.get_merchant_by_address(&merchant);
+    assert_eq!(merchant_data.id, 2);
+    assert_eq!(merchant_data.address, merchant);
+    assert_eq!(merchant_data.date_registered, 200);
+}
+
+#[test]
+fn test_get_merchant_count_empty() {
+    let (_env, client, _admin) = setup_test();
+    assert_eq!(client.get_merchant_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_get_merchant_id_unknown_address() {
+    let (env, client, _admin) = setup_test();
+    client.get_merchant_id(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_get_merchant_by_unknown_address() {
+    let (env, client, _admin) = setup_test();
+    client.get_merchant_by_address(&Address::generate(&env));
+}
+
+#[test]
+fn test_filter_by_registration_date() {
+    let (env, client, _admin) = setup_test();
+    let early = register_at(&env, &client, 100);
+    let middle = register_at(&env, &client, 200);
+    let late = register_at(&env, &client, 300);
+
+    let from_middle = client.get_merchants(&MerchantFilter {
+        registered_from: Some(200),
+        ..empty_filter()
+    });
+    assert_eq!(from_middle.len(), 2);
+    assert_eq!(from_middle.get(0).unwrap().address, middle);
+    assert_eq!(from_middle.get(1).unwrap().address, late);
+
+    let until_middle = client.get_merchants(&MerchantFilter {
+        registered_until: Some(200),
+        ..empty_filter()
+    });
+    assert_eq!(until_middle.len(), 2);
+    assert_eq!(until_middle.get(0).unwrap().address, early);
+
+    let window = client.get_merchants(&MerchantFilter {
+        registered_from: Some(150),
+        registered_until: Some(250),
+        ..empty_filter()
+    });
+    assert_eq!(window.len(), 1);
+    assert_eq!(window.get(0).unwrap().address, middle);
+}
+
+#[test]
+fn test_filter_by_account_and_key() {
+    let (env, client, _admin) = setup_test();
+    let with_account = register_at(&env, &client, 100);
+    let with_key = register_at(&env, &client, 100);
+
+    client.deploy_merchant_account(&with_account);
+    client.set_merchant_key(&with_key, &BytesN::from_array(&env, &[1u8; 32]));
+
+    let has_account = client.get_merchants(&MerchantFilter {
+        has_account: Some(true),
+        ..empty_filter()
+    });
+    assert_eq!(has_account.len(), 1);
+    assert_eq!(has_account.get(0).unwrap().address, with_account);
+
+    let no_account = client.get_merchants(&MerchantFilter {
+        has_account: Some(false),
+        ..empty_filter()
+    });
+    assert_eq!(no_account.len(), 1);
+    assert_eq!(no_account.get(0).unwrap().address, with_key);
+
+    let has_key = client.get_merchants(&MerchantFilter {
+        has_key: Some(true),
+        ..empty_filter()
+    });
+    assert_eq!(has_key.len(), 1);
+    assert_eq!(has_key.get(0).unwrap().address, with_key);
+
+    let account_without_key = client.get_merchants(&MerchantFilter {
+        has_account: Some(true),
+        has_key: Some(false),
+        ..empty_filter()
+    });
+    assert_eq!(account_without_key.len(), 1);
+    assert_eq!(account_without_key.get(0).unwrap().address, with_account);
+}
@@ -0,0 +1,155 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::InvoiceStatus;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, admin, merchant, token)
+}
+
+fn create_invoice(env: &Env, client: &ShadeClient, merchant: &Address, token: &Address) -> u64 {
+    client.create_invoice(merchant, &String::from_str(env, "Order"), &1000, token)
+}
+
+fn pay(env: &Env, client: &ShadeClient, token: &Address, invoice_id: u64) {
+    let payer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&payer, &1000);
+    client.pay_invoice(&payer, &invoice_id);
+}
+
+#[test]
+fn test_pause_merchant() {
+    let (env, client, _admin, merchant, _token) = setup_test();
+    let message = String::from_str(&env, "Back on Monday");
+
+    client.pause_merchant(&merchant, &Some(5_000), &message);
+
+    assert!(client.is_merchant_paused(&1));
+    let pause = client.get_merchant_pause(&1).unwrap();
+    assert_eq!(pause.paused_at, 1_000);
+    assert_eq!(pause.resume_at, Some(5_000));
+    assert_eq!(pause.message, message);
+}
+
+#[test]
+fn test_resume_merchant() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    client.pause_merchant(&merchant, &None, &String::from_str(&env, "Closed"));
+    client.resume_merchant(&merchant);
+
+    assert!(!client.is_merchant_paused(&1));
+    assert_eq!(client.get_merchant_pause(&1), None);
+    create_invoice(&env, &client, &merchant, &token);
+}
+
+#[test]
+fn test_pause_auto_resumes() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    client.pause_merchant(&merchant, &Some(5_000), &String::from_str(&env, ""));
+    env.ledger().set_timestamp(5_000);
+
+    assert!(!client.is_merchant_paused(&1));
+    let invoice_id = create_invoice(&env, &client, &merchant, &token);
+    pay(&env, &client, &token, invoice_id);
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+}
+
+#[test]
+fn test_paused_merchant_can_still_void() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    let invoice_id = create_invoice(&env, &client, &merchant, &token);
+    client.pause_merchant(&merchant, &None, &String::from_str(&env, "Closed"));
+    client.void_invoice(&merchant, &invoice_id);
+
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_pause_independent_of_global_pause() {
+    let (env, client, admin, merchant, token) = setup_test();
+
+    client.pause_merchant(&merchant, &None, &String::from_str(&env, "Closed"));
+    client.pause(&admin);
+    client.unpause(&admin);
+
+    assert!(!client.is_paused());
+    assert!(client.is_merchant_paused(&1));
+    assert!(client
+        .try_create_invoice(&merchant, &String::from_str(&env, "Order"), &1000, &token)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #38)")]
+fn test_create_invoice_while_paused() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    client.pause_merchant(&merchant, &None, &String::from_str(&env, "Closed"));
+    create_invoice(&env, &client, &merchant, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #38)")]
+fn test_pay_invoice_while_paused() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    let invoice_id = create_invoice(&env, &client, &merchant, &token);
+    client.pause_merchant(&merchant, &Some(5_000), &String::from_str(&env, "Closed"));
+    pay(&env, &client, &token, invoice_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #39)")]
+fn test_resume_when_not_paused() {
+    let (_env, client, _admin, merchant, _token) = setup_test();
+    client.resume_merchant(&merchant);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #40)")]
+fn test_pause_with_past_resume_time() {
+    let (env, client, _admin, merchant, _token) = setup_test();
+    client.pause_merchant(&merchant, &Some(1_000), &String::from_str(&env, "Closed"));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_pause_unregistered_merchant() {
+    let (env, client, _admin, _merchant, _token) = setup_test();
+    client.pause_merchant(
+        &Address::generate(&env),
+        &None,
+        &String::from_str(&env, "Closed"),
+    );
+}
@@ -0,0 +1,181 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::types::{MerchantFilter, MerchantProfile};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Symbol, TryIntoVal, Val};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    (env, client, contract_id, admin)
+}
+
+fn set_profile(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    merchant: &Address,
+    name: &str,
+    category: &str,
+    country_code: &str,
+) {
+    client.set_merchant_profile(
+        merchant,
+        &MerchantProfile {
+            name: String::from_str(env, name),
+            website: String::from_str(env, "https://example.com"),
+            support_email_hash: BytesN::from_array(env, &[7u8; 32]),
+            logo_uri: String::from_str(env, "ipfs://logo"),
+            category: String::from_str(env, category),
+            country_code: String::from_str(env, country_code),
+        },
+    );
+}
+
+fn empty_filter() -> MerchantFilter {
+    MerchantFilter {
+        is_active: None,
+        is_verified: None,
+        category: None,
+        country_code: None,
+        registered_from: None,
+        registered_until: None,
+        has_account: None,
+        has_key: None,
+    }
+}
+
+#[test]
+fn test_set_and_get_merchant_profile() {
+    let (env, client, contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    assert_eq!(client.get_merchant_profile(&1), None);
+
+    set_profile(&env, &client, &merchant, "Corner Cafe", "food", "NG");
+
+    let events = env.events().all();
+    let (event_contract_id, topics, data) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+
+    let event_name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(
+        event_name,
+        Symbol::new(&env, "merchant_profile_updated_event")
+    );
+
+    let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let merchant_id: u64 = data_map
+        .get(Symbol::new(&env, "merchant_id"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(merchant_id, 1);
+
+    let profile = client.get_merchant_profile(&1).unwrap();
+    assert_eq!(profile.name, String::from_str(&env, "Corner Cafe"));
+    assert_eq!(
+        profile.website,
+        String::from_str(&env, "https://example.com")
+    );
+    assert_eq!(
+        profile.support_email_hash,
+        BytesN::from_array(&env, &[7u8; 32])
+    );
+    assert_eq!(profile.logo_uri, String::from_str(&env, "ipfs://logo"));
+    assert_eq!(profile.category, String::from_str(&env, "food"));
+    assert_eq!(profile.country_code, String::from_str(&env, "NG"));
+
+    // Updating replaces the previous record
+    set_profile(&env, &client, &merchant, "Corner Bistro", "food", "GH");
+    let profile = client.get_merchant_profile(&1).unwrap();
+    assert_eq!(profile.name, String::from_str(&env, "Corner Bistro"));
+    assert_eq!(profile.country_code, String::from_str(&env, "GH"));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_set_merchant_profile_unregistered() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let stranger = Address::generate(&env);
+    set_profile(&env, &client, &stranger, "Nobody", "food", "NG");
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_set_merchant_profile_empty_name() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    set_profile(&env, &client, &merchant, "", "food", "NG");
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_set_merchant_profile_name_too_long() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let long_name = "x".repeat(65);
+    set_profile(&env, &client, &merchant, &long_name, "food", "NG");
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #25)")]
+fn test_set_merchant_profile_invalid_country_code() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    set_profile(&env, &client, &merchant, "Corner Cafe", "food", "NGA");
+}
+
+#[test]
+fn test_get_merchants_filters_by_category_and_country() {
+    let (env, client, _contract_id, _admin) = setup_test();
+
+    let cafe = Address::generate(&env);
+    let bookshop = Address::generate(&env);
+    let bakery = Address::generate(&env);
+    let no_profile = Address::generate(&env);
+    client.register_merchant(&cafe);
+    client.register_merchant(&bookshop);
+    client.register_merchant(&bakery);
+    client.register_merchant(&no_profile);
+
+    set_profile(&env, &client, &cafe, "Cafe", "food", "NG");
+    set_profile(&env, &client, &bookshop, "Books", "retail", "NG");
+    set_profile(&env, &client, &bakery, "Bakery", "food", "KE");
+
+    let food = client.get_merchants(&MerchantFilter {
+        category: Some(String::from_str(&env, "food")),
+        ..empty_filter()
+    });
+    assert_eq!(food.len(), 2);
+    assert_eq!(food.get(0).unwrap().address, cafe);
+    assert_eq!(food.get(1).unwrap().address, bakery);
+
+    let nigeria_food = client.get_merchants(&MerchantFilter {
+        category: Some(String::from_str(&env, "food")),
+        country_code: Some(String::from_str(&env, "NG")),
+        ..empty_filter()
+    });
+    assert_eq!(nigeria_food.len(), 1);
+    assert_eq!(nigeria_food.get(0).unwrap().address, cafe);
+
+    assert_eq!(client.get_merchants(&empty_filter()).len(), 4);
+}
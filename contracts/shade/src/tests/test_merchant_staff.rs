@@ -0,0 +1,243 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{InvoiceStatus, MerchantRole};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, merchant, token)
+}
+
+fn create_and_pay_invoice(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> u64 {
+    let description = String::from_str(env, "Staff");
+    let invoice_id = client.create_invoice(merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &amount);
+    client.pay_invoice(&customer, &invoice_id);
+
+    invoice_id
+}
+
+#[test]
+fn test_cashier_creates_and_voids_invoices() {
+    let (env, client, merchant, token) = setup_test();
+
+    let cashier = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &cashier, &MerchantRole::Cashier, &None);
+    assert!(client.has_merchant_role(&cashier, &MerchantRole::Cashier));
+
+    let description = String::from_str(&env, "Till 1");
+    let invoice_id = client.create_invoice(&cashier, &description, &100, &token);
+    assert_eq!(client.get_invoice(&invoice_id).merchant_id, 1);
+
+    client.void_invoice(&cashier, &invoice_id);
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_refund_manager_refunds_within_limit() {
+    let (env, client, merchant, token) = setup_test();
+
+    let refund_manager = Address::generate(&env);
+    client.grant_merchant_role(
+        &merchant,
+        &refund_manager,
+        &MerchantRole::RefundManager,
+        &Some(300),
+    );
+
+    let invoice_id = create_and_pay_invoice(&env, &client, &merchant, &token, 1000);
+
+    client.refund_invoice_partial(&refund_manager, &invoice_id, &300);
+    assert_eq!(client.get_invoice(&invoice_id).amount_refunded, 300);
+
+    let result = client.try_refund_invoice_partial(&refund_manager, &invoice_id, &301);
+    assert!(result.is_err());
+
+    // The merchant itself is not limited
+    client.refund_invoice_partial(&merchant, &invoice_id, &600);
+    assert_eq!(client.get_invoice(&invoice_id).amount_refunded, 900);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #34)")]
+fn test_refund_manager_over_limit() {
+    let (env, client, merchant, token) = setup_test();
+
+    let refund_manager = Address::generate(&env);
+    client.grant_merchant_role(
+        &merchant,
+        &refund_manager,
+        &MerchantRole::RefundManager,
+        &Some(300),
+    );
+
+    let invoice_id = create_and_pay_invoice(&env, &client, &merchant, &token, 1000);
+    client.refund_invoice(&refund_manager, &invoice_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #34)")]
+fn test_refund_manager_limit_is_cumulative_per_invoice() {
+    let (env, client, merchant, token) = setup_test();
+
+    let refund_manager = Address::generate(&env);
+    client.grant_merchant_role(
+        &merchant,
+        &refund_manager,
+        &MerchantRole::RefundManager,
+        &Some(300),
+    );
+
+    let invoice_id = create_and_pay_invoice(&env, &client, &merchant, &token, 1000);
+
+    // Each call is under the limit, their sum is not
+    client.refund_invoice_partial(&refund_manager, &invoice_id, &200);
+    client.refund_invoice_partial(&refund_manager, &invoice_id, &200);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_accountant_cannot_create_invoices() {
+    let (env, client, merchant, token) = setup_test();
+
+    let accountant = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &accountant, &MerchantRole::Accountant, &None);
+    assert!(client.has_merchant_role(&accountant, &MerchantRole::Accountant));
+
+    let description = String::from_str(&env, "Not allowed");
+    client.create_invoice(&accountant, &description, &100, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_cashier_cannot_refund() {
+    let (env, client, merchant, token) = setup_test();
+
+    let cashier = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &cashier, &MerchantRole::Cashier, &None);
+
+    let invoice_id = create_and_pay_invoice(&env, &client, &merchant, &token, 1000);
+    client.refund_invoice_partial(&cashier, &invoice_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_staff_cannot_act_for_other_merchant() {
+    let (env, client, merchant, token) = setup_test();
+
+    let cashier = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &cashier, &MerchantRole::Cashier, &None);
+
+    let other_merchant = Address::generate(&env);
+    client.register_merchant(&other_merchant);
+    let description = String::from_str(&env, "Other");
+    let invoice_id = client.create_invoice(&other_merchant, &description, &100, &token);
+
+    client.void_invoice(&cashier, &invoice_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #33)")]
+fn test_staff_belongs_to_one_merchant() {
+    let (env, client, merchant, _token) = setup_test();
+
+    let cashier = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &cashier, &MerchantRole::Cashier, &None);
+
+    let other_merchant = Address::generate(&env);
+    client.register_merchant(&other_merchant);
+    client.grant_merchant_role(&other_merchant, &cashier, &MerchantRole::Cashier, &None);
+}
+
+#[test]
+fn test_staff_of_closed_merchant_are_released() {
+    let (env, client, merchant, token) = setup_test();
+
+    let cashier = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &cashier, &MerchantRole::Cashier, &None);
+    client.close_merchant(&merchant);
+
+    assert_eq!(client.get_staff_member(&cashier), None);
+    assert!(client
+        .try_create_invoice(&cashier, &String::from_str(&env, "Stale"), &100, &token)
+        .is_err());
+
+    let other_merchant = Address::generate(&env);
+    client.register_merchant(&other_merchant);
+    client.grant_merchant_role(&other_merchant, &cashier, &MerchantRole::Cashier, &None);
+
+    assert_eq!(client.get_staff_member(&cashier).unwrap().merchant_id, 2);
+    let invoice_id =
+        client.create_invoice(&cashier, &String::from_str(&env, "Hired"), &100, &token);
+    assert_eq!(client.get_invoice(&invoice_id).merchant_id, 2);
+}
+
+#[test]
+fn test_revoke_merchant_role() {
+    let (env, client, merchant, token) = setup_test();
+
+    let staff = Address::generate(&env);
+    client.grant_merchant_role(&merchant, &staff, &MerchantRole::Cashier, &None);
+    client.grant_merchant_role(&merchant, &staff, &MerchantRole::Accountant, &None);
+
+    client.revoke_merchant_role(&merchant, &staff, &MerchantRole::Cashier);
+    assert!(!client.has_merchant_role(&staff, &MerchantRole::Cashier));
+    assert!(client.has_merchant_role(&staff, &MerchantRole::Accountant));
+
+    let description = String::from_str(&env, "Revoked");
+    assert!(client
+        .try_create_invoice(&staff, &description, &100, &token)
+        .is_err());
+
+    // Without roles the staff member is released
+    client.revoke_merchant_role(&merchant, &staff, &MerchantRole::Accountant);
+    assert_eq!(client.get_staff_member(&staff), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_refund_manager_requires_limit() {
+    let (env, client, merchant, _token) = setup_test();
+
+    let refund_manager = Address::generate(&env);
+    client.grant_merchant_role(
+        &merchant,
+        &refund_manager,
+        &MerchantRole::RefundManager,
+        &None,
+    );
+}
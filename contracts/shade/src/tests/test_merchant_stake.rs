@@ -0,0 +1,213 @@
+#![cfg(test)]
+
+use crate::components::stake::STAKE_COOLDOWN;
+use crate::shade::{Shade, ShadeClient};
+use crate::types::{Role, StakeRequirement};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env};
+
+const STAKE: i128 = 5_000;
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+    client.set_stake_requirement(
+        &admin,
+        &Some(StakeRequirement {
+            token: token.clone(),
+            amount: STAKE,
+        }),
+    );
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    token::StellarAssetClient::new(&env, &token).mint(&merchant, &10_000);
+
+    (env, client, admin, merchant, token)
+}
+
+fn operator(env: &Env, client: &ShadeClient, admin: &Address) -> Address {
+    let operator = Address::generate(env);
+    client.grant_role(admin, &operator, &Role::Operator);
+    operator
+}
+
+fn balance(env: &Env, token: &Address, owner: &Address) -> i128 {
+    token::TokenClient::new(env, token).balance(owner)
+}
+
+#[test]
+fn test_deposit_stake_and_verify() {
+    let (env, client, admin, merchant, token) = setup_test();
+
+    client.deposit_stake(&merchant, &3_000);
+    client.deposit_stake(&merchant, &2_000);
+
+    let stake = client.get_merchant_stake(&1).unwrap();
+    assert_eq!(stake.token, token);
+    assert_eq!(stake.amount, STAKE);
+    assert_eq!(stake.slashed, 0);
+    assert_eq!(stake.unlock_at, None);
+    assert_eq!(balance(&env, &token, &merchant), 5_000);
+    assert_eq!(balance(&env, &token, &client.address), STAKE);
+
+    client.verify_merchant(&admin, &1, &true);
+    assert!(client.is_merchant_verified(&1));
+}
+
+#[test]
+fn test_verify_without_requirement() {
+    let (_env, client, admin, _merchant, _token) = setup_test();
+
+    client.set_stake_requirement(&admin, &None);
+    assert_eq!(client.get_stake_requirement(), None);
+
+    client.verify_merchant(&admin, &1, &true);
+    assert!(client.is_merchant_verified(&1));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #41)")]
+fn test_verify_with_insufficient_stake() {
+    let (_env, client, admin, merchant, _token) = setup_test();
+
+    client.deposit_stake(&merchant, &(STAKE - 1));
+    client.verify_merchant(&admin, &1, &true);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #41)")]
+fn test_set_tier_requires_stake() {
+    let (_env, client, admin, _merchant, _token) = setup_test();
+    client.set_merchant_tier(&admin, &1, &crate::types::VerificationTier::Full);
+}
+
+#[test]
+fn test_revoking_verification_ignores_stake() {
+    let (_env, client, admin, _merchant, _token) = setup_test();
+    client.verify_merchant(&admin, &1, &false);
+    assert!(!client.is_merchant_verified(&1));
+}
+
+#[test]
+fn test_slash_stake() {
+    let (env, client, admin, merchant, token) = setup_test();
+    let operator = operator(&env, &client, &admin);
+    let victim = Address::generate(&env);
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.slash_stake(&operator, &1, &1_500, &victim);
+
+    let stake = client.get_merchant_stake(&1).unwrap();
+    assert_eq!(stake.amount, 3_500);
+    assert_eq!(stake.slashed, 1_500);
+    assert_eq!(balance(&env, &token, &victim), 1_500);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #43)")]
+fn test_slash_more_than_stake() {
+    let (env, client, admin, merchant, _token) = setup_test();
+    let operator = operator(&env, &client, &admin);
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.slash_stake(&operator, &1, &(STAKE + 1), &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_slash_while_contract_paused() {
+    let (env, client, admin, merchant, _token) = setup_test();
+    let operator = operator(&env, &client, &admin);
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.pause(&admin);
+    client.slash_stake(&operator, &1, &100, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_slash_requires_operator() {
+    let (env, client, _admin, merchant, _token) = setup_test();
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.slash_stake(&merchant, &1, &100, &Address::generate(&env));
+}
+
+#[test]
+fn test_withdraw_after_closure_cooldown() {
+    let (env, client, admin, merchant, token) = setup_test();
+    let operator = operator(&env, &client, &admin);
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.close_merchant(&merchant);
+    assert_eq!(
+        client.get_merchant_stake(&1).unwrap().unlock_at,
+        Some(1_000 + STAKE_COOLDOWN)
+    );
+
+    // Still slashable during the cooldown
+    client.slash_stake(&operator, &1, &1_000, &Address::generate(&env));
+
+    env.ledger().set_timestamp(1_000 + STAKE_COOLDOWN);
+    client.withdraw_stake(&1);
+
+    assert_eq!(client.get_merchant_stake(&1).unwrap().amount, 0);
+    assert_eq!(balance(&env, &token, &merchant), 9_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #42)")]
+fn test_withdraw_while_active() {
+    let (_env, client, _admin, merchant, _token) = setup_test();
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.withdraw_stake(&1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #42)")]
+fn test_withdraw_during_cooldown() {
+    let (env, client, _admin, merchant, _token) = setup_test();
+
+    client.deposit_stake(&merchant, &STAKE);
+    client.close_merchant(&merchant);
+
+    env.ledger().set_timestamp(1_000 + STAKE_COOLDOWN - 1);
+    client.withdraw_stake(&1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #44)")]
+fn test_deposit_without_requirement() {
+    let (_env, client, admin, merchant, _token) = setup_test();
+
+    client.set_stake_requirement(&admin, &None);
+    client.deposit_stake(&merchant, &STAKE);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_set_requirement_unaccepted_token() {
+    let (env, client, admin, _merchant, _token) = setup_test();
+
+    client.set_stake_requirement(
+        &admin,
+        &Some(StakeRequirement {
+            token: Address::generate(&env),
+            amount: STAKE,
+        }),
+    );
+}
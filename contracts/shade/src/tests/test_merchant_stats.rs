@@ -0,0 +1,171 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{DataKey, TokenVolume};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = create_accepted_token(&env, &client, &admin);
+    client.set_fee(&admin, &token, &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, admin, merchant, token)
+}
+
+fn create_accepted_token(env: &Env, client: &ShadeClient, admin: &Address) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.add_accepted_token(admin, &token);
+    token
+}
+
+fn create_invoice(
+    env: &Env,
+    client: &ShadeClient,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> u64 {
+    client.create_invoice(merchant, &String::from_str(env, "Order"), &amount, token)
+}
+
+fn pay(env: &Env, client: &ShadeClient, token: &Address, invoice_id: u64) -> Address {
+    let payer = Address::generate(env);
+    let amount = client.get_invoice(&invoice_id).amount;
+    token::StellarAssetClient::new(env, token).mint(&payer, &amount);
+    client.pay_invoice(&payer, &invoice_id);
+    payer
+}
+
+#[test]
+fn test_new_merchant_has_empty_stats() {
+    let (_env, client, _admin, _merchant, _token) = setup_test();
+
+    let stats = client.get_merchant_stats(&1);
+    assert_eq!(stats.invoice_count, 0);
+    assert_eq!(stats.pending_count, 0);
+    assert_eq!(stats.first_payment_at, None);
+    assert_eq!(stats.last_payment_at, None);
+    assert!(stats.volumes.is_empty());
+}
+
+#[test]
+fn test_stats_track_invoice_lifecycle() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let start = env.ledger().timestamp();
+
+    let paid_id = create_invoice(&env, &client, &merchant, &token, 1000);
+    let refunded_id = create_invoice(&env, &client, &merchant, &token, 2000);
+    let voided_id = create_invoice(&env, &client, &merchant, &token, 300);
+    create_invoice(&env, &client, &merchant, &token, 400);
+
+    pay(&env, &client, &token, paid_id);
+    env.ledger().set_timestamp(start + 60);
+    pay(&env, &client, &token, refunded_id);
+    client.void_invoice(&merchant, &voided_id);
+
+    client.refund_invoice_partial(&merchant, &refunded_id, &500);
+    client.refund_invoice_partial(&merchant, &paid_id, &1000);
+
+    let stats = client.get_merchant_stats(&1);
+    assert_eq!(stats.invoice_count, 4);
+    assert_eq!(stats.pending_count, 1);
+    assert_eq!(stats.paid_count, 0);
+    assert_eq!(stats.cancelled_count, 1);
+    assert_eq!(stats.refunded_count, 1);
+    assert_eq!(stats.partially_refunded_count, 1);
+    assert_eq!(stats.first_payment_at, Some(start));
+    assert_eq!(stats.last_payment_at, Some(start + 60));
+
+    assert_eq!(stats.volumes.len(), 1);
+    let volume = stats.volumes.get(0).unwrap();
+    assert_eq!(volume.token, token);
+    assert_eq!(volume.gross_volume, 3000);
+    assert_eq!(volume.fees_paid, 150);
+    assert_eq!(volume.refunded, 1500);
+}
+
+#[test]
+fn test_stats_split_volume_per_token() {
+    let (env, client, admin, merchant, token) = setup_test();
+    let other_token = create_accepted_token(&env, &client, &admin);
+
+    let first = create_invoice(&env, &client, &merchant, &token, 1000);
+    let second = create_invoice(&env, &client, &merchant, &other_token, 700);
+    pay(&env, &client, &token, first);
+    pay(&env, &client, &other_token, second);
+
+    let stats = client.get_merchant_stats(&1);
+    assert_eq!(stats.paid_count, 2);
+    assert_eq!(stats.volumes.len(), 2);
+
+    let volume = stats.volumes.get(1).unwrap();
+    assert_eq!(volume.token, other_token);
+    assert_eq!(volume.gross_volume, 700);
+    assert_eq!(volume.fees_paid, 0);
+}
+
+#[test]
+fn test_stats_count_invoices_voided_on_close() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    create_invoice(&env, &client, &merchant, &token, 1000);
+    create_invoice(&env, &client, &merchant, &token, 2000);
+    client.close_merchant(&merchant);
+
+    let stats = client.get_merchant_stats(&1);
+    assert_eq!(stats.invoice_count, 2);
+    assert_eq!(stats.pending_count, 0);
+    assert_eq!(stats.cancelled_count, 2);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_stats_unknown_merchant() {
+    let (_env, client, _admin, _merchant, _token) = setup_test();
+    client.get_merchant_stats(&99);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #21)")]
+fn test_volume_overflow_is_rejected() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    let invoice_id = create_invoice(&env, &client, &merchant, &token, 1000);
+
+    let mut stats = client.get_merchant_stats(&1);
+    stats.volumes.push_back(TokenVolume {
+        token: token.clone(),
+        gross_volume: i128::MAX,
+        fees_paid: 0,
+        refunded: 0,
+    });
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantStats(1), &stats);
+    });
+
+    pay(&env, &client, &token, invoice_id);
+}
@@ -0,0 +1,319 @@
+#![cfg(test)]
+
+use crate::components::limits::LIMIT_DAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{LimitHeadroom, TierLimits, VerificationTier};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    (env, client, admin, token)
+}
+
+fn register_merchant_with_account(env: &Env, client: &ShadeClient<'_>) -> Address {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+    merchant
+}
+
+fn pay(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> u64 {
+    let description = String::from_str(env, "Tiered");
+    let invoice_id = client.create_invoice(merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &amount);
+    client.pay_invoice(&customer, &invoice_id);
+
+    invoice_id
+}
+
+#[test]
+fn test_tier_follows_verification() {
+    let (env, client, admin, _token) = setup_test();
+    register_merchant_with_account(&env, &client);
+
+    assert_eq!(client.get_merchant_tier(&1), VerificationTier::Unverified);
+
+    client.verify_merchant(&admin, &1, &true);
+    assert_eq!(client.get_merchant_tier(&1), VerificationTier::Basic);
+
+    client.set_merchant_tier(&admin, &1, &VerificationTier::Full);
+    assert_eq!(client.get_merchant_tier(&1), VerificationTier::Full);
+    assert!(client.is_merchant_verified(&1));
+
+    // Re-verifying does not downgrade an explicit tier
+    client.verify_merchant(&admin, &1, &true);
+    assert_eq!(client.get_merchant_tier(&1), VerificationTier::Full);
+
+    client.verify_merchant(&admin, &1, &false);
+    assert_eq!(client.get_merchant_tier(&1), VerificationTier::Unverified);
+
+    client.set_merchant_tier(&admin, &1, &VerificationTier::Basic);
+    client.set_merchant_tier(&admin, &1, &VerificationTier::Unverified);
+    assert!(!client.is_merchant_verified(&1));
+}
+
+#[test]
+fn test_invoice_amount_limit() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Unverified,
+        &token,
+        &TierLimits {
+            max_invoice_amount: Some(500),
+            max_volume_30d: None,
+        },
+    );
+
+    let description = String::from_str(&env, "Too big");
+    assert!(client
+        .try_create_invoice(&merchant, &description, &501, &token)
+        .is_err());
+    client.create_invoice(&merchant, &description, &500, &token);
+
+    // Upgrading the tier lifts the limit
+    client.set_merchant_tier(&admin, &1, &VerificationTier::Full);
+    client.create_invoice(&merchant, &description, &10_000, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #30)")]
+fn test_create_invoice_over_limit() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Unverified,
+        &token,
+        &TierLimits {
+            max_invoice_amount: Some(500),
+            max_volume_30d: None,
+        },
+    );
+
+    let description = String::from_str(&env, "Too big");
+    client.create_invoice(&merchant, &description, &501, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #30)")]
+fn test_payment_checks_limit_set_after_creation() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+
+    let description = String::from_str(&env, "Created before limits");
+    let invoice_id = client.create_invoice(&merchant, &description, &1_000, &token);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Unverified,
+        &token,
+        &TierLimits {
+            max_invoice_amount: Some(500),
+            max_volume_30d: None,
+        },
+    );
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1_000);
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+#[test]
+fn test_rolling_volume_limit_and_headroom() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+    client.verify_merchant(&admin, &1, &true);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Basic,
+        &token,
+        &TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: Some(1_000),
+        },
+    );
+
+    env.ledger().set_timestamp(100 * LIMIT_DAY);
+    pay(&env, &client, &merchant, &token, 600);
+
+    assert_eq!(
+        client.get_limit_headroom(&1, &token),
+        LimitHeadroom {
+            tier: VerificationTier::Basic,
+            max_invoice_amount: None,
+            volume_remaining: Some(400),
+        }
+    );
+
+    // Ten days later the first payment still counts
+    env.ledger().set_timestamp(110 * LIMIT_DAY);
+    let description = String::from_str(&env, "Over volume");
+    let invoice_id = client.create_invoice(&merchant, &description, &500, &token);
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &500);
+    assert!(client.try_pay_invoice(&customer, &invoice_id).is_err());
+
+    pay(&env, &client, &merchant, &token, 400);
+    assert_eq!(
+        client.get_limit_headroom(&1, &token).volume_remaining,
+        Some(0)
+    );
+
+    // Once the first payment leaves the 30 day window it frees up headroom
+    env.ledger().set_timestamp(130 * LIMIT_DAY);
+    assert_eq!(
+        client.get_limit_headroom(&1, &token).volume_remaining,
+        Some(600)
+    );
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+#[test]
+fn test_refund_frees_rolling_volume() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+    client.verify_merchant(&admin, &1, &true);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Basic,
+        &token,
+        &TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: Some(1_000),
+        },
+    );
+
+    env.ledger().set_timestamp(100 * LIMIT_DAY);
+    let invoice_id = pay(&env, &client, &merchant, &token, 800);
+
+    env.ledger().set_timestamp(105 * LIMIT_DAY);
+    client.refund_invoice_partial(&merchant, &invoice_id, &300);
+    assert_eq!(
+        client.get_limit_headroom(&1, &token).volume_remaining,
+        Some(500)
+    );
+
+    pay(&env, &client, &merchant, &token, 500);
+    assert_eq!(
+        client.get_limit_headroom(&1, &token).volume_remaining,
+        Some(0)
+    );
+}
+
+#[test]
+fn test_rolling_volume_resets_after_idle_window() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+    client.verify_merchant(&admin, &1, &true);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Basic,
+        &token,
+        &TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: Some(1_000),
+        },
+    );
+
+    env.ledger().set_timestamp(100 * LIMIT_DAY);
+    pay(&env, &client, &merchant, &token, 1_000);
+
+    // A bucket reused by a later day does not carry the old volume over
+    env.ledger().set_timestamp(160 * LIMIT_DAY);
+    assert_eq!(
+        client.get_limit_headroom(&1, &token).volume_remaining,
+        Some(1_000)
+    );
+    pay(&env, &client, &merchant, &token, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_token_checked_before_volume() {
+    let (env, client, admin, token) = setup_test();
+    let merchant = register_merchant_with_account(&env, &client);
+
+    let description = String::from_str(&env, "Revoked token");
+    let invoice_id = client.create_invoice(&merchant, &description, &1_000, &token);
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Unverified,
+        &token,
+        &TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: Some(500),
+        },
+    );
+    client.remove_accepted_token(&admin, &token);
+
+    let customer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&customer, &1_000);
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_set_tier_limits_non_admin() {
+    let (env, client, _admin, token) = setup_test();
+
+    let non_admin = Address::generate(&env);
+    client.set_tier_limits(
+        &non_admin,
+        &VerificationTier::Basic,
+        &token,
+        &TierLimits {
+            max_invoice_amount: Some(1),
+            max_volume_30d: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_set_tier_limits_rejects_non_positive() {
+    let (_env, client, admin, token) = setup_test();
+
+    client.set_tier_limits(
+        &admin,
+        &VerificationTier::Basic,
+        &token,
+        &TierLimits {
+            max_invoice_amount: None,
+            max_volume_30d: Some(0),
+        },
+    );
+}
@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{token, vec, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, admin, merchant)
+}
+
+fn create_accepted_token(env: &Env, client: &ShadeClient, admin: &Address) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.add_accepted_token(admin, &token);
+    token
+}
+
+fn create_invoice(env: &Env, client: &ShadeClient, merchant: &Address, token: &Address) -> u64 {
+    client.create_invoice(merchant, &String::from_str(env, "Order"), &1000, token)
+}
+
+#[test]
+fn test_merchant_tokens_default_to_global_list() {
+    let (env, client, admin, _merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    assert_eq!(
+        client.get_merchant_tokens(&1),
+        vec![&env, usdc.clone(), xlm.clone()]
+    );
+    assert!(client.is_merchant_token(&1, &usdc));
+    assert!(client.is_merchant_token(&1, &xlm));
+}
+
+#[test]
+fn test_set_merchant_tokens() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc.clone(), usdc.clone()]);
+
+    assert_eq!(client.get_merchant_tokens(&1), vec![&env, usdc.clone()]);
+    assert!(client.is_merchant_token(&1, &usdc));
+    assert!(!client.is_merchant_token(&1, &xlm));
+
+    let invoice_id = create_invoice(&env, &client, &merchant, &usdc);
+    assert_eq!(client.get_invoice(&invoice_id).token, usdc);
+}
+
+#[test]
+fn test_clear_merchant_tokens() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc.clone()]);
+    client.set_merchant_tokens(&merchant, &vec![&env]);
+
+    assert_eq!(
+        client.get_merchant_tokens(&1),
+        vec![&env, usdc, xlm.clone()]
+    );
+    create_invoice(&env, &client, &merchant, &xlm);
+}
+
+#[test]
+fn test_global_removal_narrows_merchant_tokens() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc.clone(), xlm.clone()]);
+    client.remove_accepted_token(&admin, &usdc);
+
+    assert_eq!(client.get_merchant_tokens(&1), vec![&env, xlm]);
+    assert!(!client.is_merchant_token(&1, &usdc));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_set_merchant_tokens_outside_global_list() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let unknown = Address::generate(&env);
+
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc, unknown]);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_create_invoice_token_not_in_allowlist() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc]);
+    create_invoice(&env, &client, &merchant, &xlm);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_create_invoice_token_not_globally_accepted() {
+    let (env, client, _admin, merchant) = setup_test();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    create_invoice(&env, &client, &merchant, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_pay_invoice_token_removed_from_allowlist() {
+    let (env, client, admin, merchant) = setup_test();
+    let usdc = create_accepted_token(&env, &client, &admin);
+    let xlm = create_accepted_token(&env, &client, &admin);
+
+    let invoice_id = create_invoice(&env, &client, &merchant, &xlm);
+    client.set_merchant_tokens(&merchant, &vec![&env, usdc]);
+
+    let payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm).mint(&payer, &1000);
+    client.pay_invoice(&payer, &invoice_id);
+}
@@ -1,8 +1,10 @@
 #![cfg(test)]
 
+use crate::components::admin::FEE_CHANGE_DELAY;
 use crate::shade::{Shade, ShadeClient};
-use crate::types::InvoiceStatus;
-use soroban_sdk::testutils::{Address as _, Events as _};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{FeeBearer, InvoiceStatus};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
 use soroban_sdk::{token, Address, Env, String};
 
 fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Address) {
@@ -17,6 +19,9 @@ fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Ad
     let admin = Address::generate(&env);
     shade_client.initialize(&admin);
 
+    let account_wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    shade_client.set_account_wasm_hash(&admin, &account_wasm_hash);
+
     // Create and register token
     let token_admin = Address::generate(&env);
     let token = env.register_stellar_asset_contract_v2(token_admin.clone());
@@ -26,6 +31,8 @@ fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Ad
 
     // Set fee to 500 bps (5%)
     shade_client.set_fee(&admin, &token.address(), &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
 
     (env, shade_client, shade_contract_id, admin, token.address())
 }
@@ -38,9 +45,8 @@ fn test_successful_payment_with_fee() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account (using a regular address as mock)
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    let merchant_account = shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice for 1000 units
     let description = String::from_str(&env, "Test Invoice");
@@ -80,9 +86,8 @@ fn test_payment_with_zero_fee() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    let merchant_account = shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice for 1000 units
     let description = String::from_str(&env, "Test Invoice");
@@ -111,14 +116,15 @@ fn test_payment_with_maximum_fee() {
 
     // Set fee to 10000 bps (100%)
     shade_client.set_fee(&admin, &token, &10000);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
 
     // Register merchant
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    let merchant_account = shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice for 1000 units
     let description = String::from_str(&env, "Test Invoice");
@@ -142,7 +148,7 @@ fn test_payment_with_maximum_fee() {
 }
 
 #[test]
-#[should_panic(expected = "HostError: Error(Contract, #15)")]
+#[should_panic(expected = "HostError: Error(Contract, #14)")]
 fn test_payment_invoice_already_paid() {
     let (env, shade_client, _shade_contract_id, _admin, token) = setup_test_with_payment();
 
@@ -150,9 +156,8 @@ fn test_payment_invoice_already_paid() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice
     let description = String::from_str(&env, "Test Invoice");
@@ -179,9 +184,8 @@ fn test_payment_insufficient_funds() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice for 1000 units
     let description = String::from_str(&env, "Test Invoice");
@@ -199,26 +203,25 @@ fn test_payment_insufficient_funds() {
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #12)")]
 fn test_payment_token_not_accepted() {
-    let (env, shade_client, _shade_contract_id, _admin, _token) = setup_test_with_payment();
+    let (env, shade_client, _shade_contract_id, admin, token) = setup_test_with_payment();
 
     // Register merchant
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
-
-    // Create invoice with unaccepted token
-    let unaccepted_token_admin = Address::generate(&env);
-    let unaccepted_token = env.register_stellar_asset_contract_v2(unaccepted_token_admin.clone());
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
 
+    // Create invoice while the token is still accepted
     let description = String::from_str(&env, "Test Invoice");
-    let invoice_id = shade_client.create_invoice(&merchant, &description, &1000, &unaccepted_token.address());
+    let invoice_id = shade_client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Token is delisted before the invoice is paid
+    shade_client.remove_accepted_token(&admin, &token);
 
     // Create customer and mint tokens
     let customer = Address::generate(&env);
-    let token_client = token::StellarAssetClient::new(&env, &unaccepted_token.address());
+    let token_client = token::StellarAssetClient::new(&env, &token);
     token_client.mint(&customer, &1000);
 
     // Customer attempts to pay invoice (should panic - token not accepted)
@@ -226,7 +229,7 @@ fn test_payment_token_not_accepted() {
 }
 
 #[test]
-#[should_panic(expected = "HostError: Error(Contract, #14)")]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
 fn test_payment_merchant_account_not_set() {
     let (env, shade_client, _shade_contract_id, _admin, token) = setup_test_with_payment();
 
@@ -257,9 +260,8 @@ fn test_payment_payer_authorization() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice
     let description = String::from_str(&env, "Test Invoice");
@@ -286,9 +288,8 @@ fn test_payment_updates_invoice_timestamps() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice
     let description = String::from_str(&env, "Test Invoice");
@@ -323,9 +324,8 @@ fn test_fee_calculation_accuracy() {
     let merchant = Address::generate(&env);
     shade_client.register_merchant(&merchant);
 
-    // Create merchant account
-    let merchant_account = Address::generate(&env);
-    shade_client.set_merchant_account(&merchant, &merchant_account);
+    // Deploy merchant account
+    let merchant_account = shade_client.deploy_merchant_account(&merchant);
 
     // Create invoice for 10000 units
     let description = String::from_str(&env, "Test Invoice");
@@ -347,3 +347,87 @@ fn test_fee_calculation_accuracy() {
     assert_eq!(shade_balance, 100); // 1% of 10000 = 100
     assert_eq!(merchant_balance, 9900); // 99% of 10000 = 9900
 }
+
+#[test]
+fn test_payment_with_limits_within_bounds() {
+    let (env, shade_client, shade_contract_id, _admin, token) = setup_test_with_payment();
+
+    // Register merchant
+    let merchant = Address::generate(&env);
+    shade_client.register_merchant(&merchant);
+
+    // Deploy merchant account
+    let merchant_account = shade_client.deploy_merchant_account(&merchant);
+
+    // Create invoice for 1000 units
+    let description = String::from_str(&env, "Test Invoice");
+    let invoice_id = shade_client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Create customer and mint tokens
+    let customer = Address::generate(&env);
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&customer, &1000);
+
+    // Limits exactly match the quoted total and fee
+    shade_client.pay_invoice_with_limits(&customer, &invoice_id, &Some(1000), &Some(50));
+
+    let token_balance_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_balance_client.balance(&shade_contract_id), 50);
+    assert_eq!(token_balance_client.balance(&merchant_account), 950);
+    assert_eq!(
+        shade_client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #20)")]
+fn test_payment_with_limits_fee_raised_after_quote() {
+    let (env, shade_client, _shade_contract_id, admin, token) = setup_test_with_payment();
+
+    // Register merchant
+    let merchant = Address::generate(&env);
+    shade_client.register_merchant(&merchant);
+
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
+
+    // Create invoice for 1000 units, quoted at a 50 unit fee
+    let description = String::from_str(&env, "Test Invoice");
+    let invoice_id = shade_client.create_invoice(&merchant, &description, &1000, &token);
+
+    // Fee is raised to 10% before the payer submits
+    shade_client.set_fee(&admin, &token, &1000);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    let customer = Address::generate(&env);
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&customer, &1000);
+
+    shade_client.pay_invoice_with_limits(&customer, &invoice_id, &None, &Some(50));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #20)")]
+fn test_payment_with_limits_total_exceeded() {
+    let (env, shade_client, _shade_contract_id, _admin, token) = setup_test_with_payment();
+
+    // Register merchant who passes the fee on to payers
+    let merchant = Address::generate(&env);
+    shade_client.register_merchant(&merchant);
+    shade_client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    // Deploy merchant account
+    shade_client.deploy_merchant_account(&merchant);
+
+    // Create invoice for 1000 units, payer owes 1050 in total
+    let description = String::from_str(&env, "Test Invoice");
+    let invoice_id = shade_client.create_invoice(&merchant, &description, &1000, &token);
+
+    let customer = Address::generate(&env);
+    let token_client = token::StellarAssetClient::new(&env, &token);
+    token_client.mint(&customer, &1050);
+
+    shade_client.pay_invoice_with_limits(&customer, &invoice_id, &Some(1000), &None);
+}
@@ -0,0 +1,284 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::components::invoice::MAX_REFUND_DURATION;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{InvoiceStatus, SplitRecipient};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+use soroban_sdk::{token, vec, Address, Env, Map, String, Symbol, TryIntoVal, Val, Vec};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, admin, merchant, token)
+}
+
+fn recipient(address: &Address, share_bps: i128) -> SplitRecipient {
+    SplitRecipient {
+        recipient: address.clone(),
+        share_bps,
+    }
+}
+
+fn partner_account(env: &Env, client: &ShadeClient) -> Address {
+    let partner = Address::generate(env);
+    client.register_merchant(&partner);
+    client.deploy_merchant_account(&partner)
+}
+
+fn create_split_invoice(
+    env: &Env,
+    client: &ShadeClient,
+    merchant: &Address,
+    token: &Address,
+    split: &Vec<SplitRecipient>,
+) -> u64 {
+    client.create_split_invoice(
+        merchant,
+        &String::from_str(env, "Revenue share"),
+        &1_000,
+        token,
+        split,
+    )
+}
+
+fn pay(env: &Env, client: &ShadeClient, token: &Address, invoice_id: u64) -> Address {
+    let payer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&payer, &1_000);
+    client.pay_invoice(&payer, &invoice_id);
+    payer
+}
+
+fn balance(env: &Env, token: &Address, owner: &Address) -> i128 {
+    token::TokenClient::new(env, token).balance(owner)
+}
+
+#[test]
+fn test_split_payment_is_escrowed() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let wallet = Address::generate(&env);
+    let partner = partner_account(&env, &client);
+
+    let split = vec![&env, recipient(&wallet, 3_000), recipient(&partner, 2_000)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    assert_eq!(client.get_invoice_split(&invoice_id), split);
+
+    pay(&env, &client, &token, invoice_id);
+
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+    assert_eq!(balance(&env, &token, &client.address), 500);
+    assert_eq!(balance(&env, &token, &wallet), 0);
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 500);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+    client.release_split_payouts(&invoice_id);
+
+    assert!(client.is_split_released(&invoice_id));
+    assert_eq!(balance(&env, &token, &wallet), 300);
+    assert_eq!(balance(&env, &token, &partner), 200);
+    assert_eq!(balance(&env, &token, &client.address), 0);
+}
+
+#[test]
+fn test_split_applies_after_fee() {
+    let (env, client, admin, merchant, token) = setup_test();
+    client.set_fee(&admin, &token, &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    let wallet = Address::generate(&env);
+    let split = vec![&env, recipient(&wallet, 3_333)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    assert!(client.get_split_payouts(&invoice_id).is_empty());
+    pay(&env, &client, &token, invoice_id);
+
+    // 950 after the 5% fee; rounding remainders stay with the merchant
+    let payout = client.get_split_payouts(&invoice_id).get(0).unwrap();
+    assert_eq!(payout.recipient, wallet);
+    assert_eq!(payout.amount, 316);
+    assert_eq!(payout.refunded, 0);
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 634);
+    assert_eq!(balance(&env, &token, &client.address), 50 + 316);
+}
+
+#[test]
+fn test_split_refund_is_proportional() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let wallet = Address::generate(&env);
+    let partner = partner_account(&env, &client);
+
+    let split = vec![&env, recipient(&wallet, 3_000), recipient(&partner, 2_000)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    let payer = pay(&env, &client, &token, invoice_id);
+
+    client.refund_invoice_partial(&merchant, &invoice_id, &400);
+
+    assert_eq!(balance(&env, &token, &payer), 400);
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 300);
+    assert_eq!(balance(&env, &token, &client.address), 300);
+
+    let payouts = client.get_split_payouts(&invoice_id);
+    assert_eq!(payouts.get(0).unwrap().refunded, 120);
+    assert_eq!(payouts.get(1).unwrap().refunded, 80);
+
+    // Only what was not refunded is released
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+    client.release_split_payouts(&invoice_id);
+    assert_eq!(balance(&env, &token, &wallet), 180);
+    assert_eq!(balance(&env, &token, &partner), 120);
+}
+
+#[test]
+fn test_fully_split_invoice_can_be_refunded() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let wallet = Address::generate(&env);
+
+    let split = vec![&env, recipient(&wallet, 10_000)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    let payer = pay(&env, &client, &token, invoice_id);
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 0);
+
+    client.refund_invoice(&merchant, &invoice_id);
+
+    assert_eq!(balance(&env, &token, &payer), 1_000);
+    assert_eq!(balance(&env, &token, &client.address), 0);
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Refunded
+    );
+}
+
+#[test]
+fn test_split_refunds_never_exceed_payout() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let wallet = Address::generate(&env);
+
+    let split = vec![&env, recipient(&wallet, 3_333)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    let payer = pay(&env, &client, &token, invoice_id);
+
+    let mut refunded = 0;
+    for amount in [1, 1, 1, 997] {
+        client.refund_invoice_partial(&merchant, &invoice_id, &amount);
+
+        let events = env.events().all();
+        for (_, topics, data) in events.iter() {
+            let name: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+            if name == Symbol::new(&env, "split_refunded_event") {
+                let data_map: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+                let share: i128 = data_map
+                    .get(Symbol::new(&env, "amount"))
+                    .unwrap()
+                    .try_into_val(&env)
+                    .unwrap();
+                refunded += share;
+            }
+        }
+    }
+
+    assert_eq!(refunded, 333);
+    assert_eq!(
+        client
+            .get_split_payouts(&invoice_id)
+            .get(0)
+            .unwrap()
+            .refunded,
+        333
+    );
+    assert_eq!(balance(&env, &token, &payer), 1_000);
+    assert_eq!(balance(&env, &token, &client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #51)")]
+fn test_split_release_within_refund_window() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let split = vec![&env, recipient(&Address::generate(&env), 3_000)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    pay(&env, &client, &token, invoice_id);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION);
+    client.release_split_payouts(&invoice_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #52)")]
+fn test_split_released_twice() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let split = vec![&env, recipient(&Address::generate(&env), 3_000)];
+    let invoice_id = create_split_invoice(&env, &client, &merchant, &token, &split);
+    pay(&env, &client, &token, invoice_id);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + MAX_REFUND_DURATION + 1);
+    client.release_split_payouts(&invoice_id);
+    client.release_split_payouts(&invoice_id);
+}
+
+#[test]
+fn test_invoice_without_split() {
+    let (env, client, _admin, merchant, token) = setup_test();
+
+    let invoice_id =
+        client.create_invoice(&merchant, &String::from_str(&env, "Plain"), &1_000, &token);
+    assert!(client.get_invoice_split(&invoice_id).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #47)")]
+fn test_split_over_100_percent() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let split = vec![
+        &env,
+        recipient(&Address::generate(&env), 6_000),
+        recipient(&Address::generate(&env), 4_001),
+    ];
+    create_split_invoice(&env, &client, &merchant, &token, &split);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #47)")]
+fn test_split_duplicate_recipient() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let wallet = Address::generate(&env);
+    let split = vec![&env, recipient(&wallet, 1_000), recipient(&wallet, 1_000)];
+    create_split_invoice(&env, &client, &merchant, &token, &split);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #47)")]
+fn test_split_zero_share() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    let split = vec![&env, recipient(&Address::generate(&env), 0)];
+    create_split_invoice(&env, &client, &merchant, &token, &split);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #47)")]
+fn test_split_empty() {
+    let (env, client, _admin, merchant, token) = setup_test();
+    create_split_invoice(&env, &client, &merchant, &token, &Vec::new(&env));
+}
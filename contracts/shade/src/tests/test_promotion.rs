@@ -0,0 +1,287 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{Promotion, PromotionAllowance};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let shade_contract_id = env.register(Shade, ());
+    let shade_client = ShadeClient::new(&env, &shade_contract_id);
+
+    let admin = Address::generate(&env);
+    shade_client.initialize(&admin);
+
+    let account_wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    shade_client.set_account_wasm_hash(&admin, &account_wasm_hash);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin.clone());
+
+    shade_client.add_accepted_token(&admin, &token.address());
+
+    // Set fee to 500 bps (5%)
+    shade_client.set_fee(&admin, &token.address(), &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    (env, shade_client, shade_contract_id, admin, token.address())
+}
+
+fn register_merchant_with_account(env: &Env, client: &ShadeClient<'_>) -> (Address, Address) {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+
+    let merchant_account = client.deploy_merchant_account(&merchant);
+
+    (merchant, merchant_account)
+}
+
+fn pay(env: &Env, client: &ShadeClient<'_>, merchant: &Address, token: &Address, amount: i128) {
+    let description = String::from_str(env, "Promo");
+    let invoice_id = client.create_invoice(merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &amount);
+    client.pay_invoice(&customer, &invoice_id);
+}
+
+#[test]
+fn test_no_promotion_by_default() {
+    let (env, client, _contract_id, _admin, token) = setup_test_with_payment();
+    let (_merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    assert_eq!(client.get_promotion_allowance(&1, &token), None);
+}
+
+#[test]
+fn test_global_time_bound_promotion_waives_fee() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let promotion = Promotion {
+        max_age: Some(1_000),
+        volume_cap: None,
+    };
+    client.set_promotion(&admin, &token, &None, &promotion);
+
+    let (merchant, merchant_account) = register_merchant_with_account(&env, &client);
+    let registered_at = env.ledger().timestamp();
+
+    env.ledger().set_timestamp(registered_at + 400);
+    assert_eq!(
+        client.get_promotion_allowance(&1, &token),
+        Some(PromotionAllowance {
+            time_remaining: Some(600),
+            volume_remaining: None,
+        })
+    );
+
+    pay(&env, &client, &merchant, &token, 1000);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 0);
+    assert_eq!(token_client.balance(&merchant_account), 1000);
+
+    // Once the holiday is over the regular fee applies again
+    env.ledger().set_timestamp(registered_at + 1_000);
+    assert_eq!(client.get_promotion_allowance(&1, &token), None);
+
+    pay(&env, &client, &merchant, &token, 1000);
+    assert_eq!(token_client.balance(&shade_contract_id), 50);
+    assert_eq!(token_client.balance(&merchant_account), 1950);
+}
+
+#[test]
+fn test_volume_bound_promotion_partially_waives_fee() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) = register_merchant_with_account(&env, &client);
+
+    let promotion = Promotion {
+        max_age: None,
+        volume_cap: Some(1_500),
+    };
+    client.set_promotion(&admin, &token, &Some(1), &promotion);
+
+    pay(&env, &client, &merchant, &token, 1000);
+    assert_eq!(
+        client.get_promotion_allowance(&1, &token),
+        Some(PromotionAllowance {
+            time_remaining: None,
+            volume_remaining: Some(500),
+        })
+    );
+
+    // Only the 500 units above the cap are charged the 5% fee
+    pay(&env, &client, &merchant, &token, 1000);
+    assert_eq!(client.get_promotion_allowance(&1, &token), None);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&shade_contract_id), 25);
+    assert_eq!(token_client.balance(&merchant_account), 1975);
+}
+
+#[test]
+fn test_merchant_promotion_overrides_global() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (_merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+    let (_other_merchant, _other_account) = register_merchant_with_account(&env, &client);
+
+    client.set_promotion(
+        &admin,
+        &token,
+        &None,
+        &Promotion {
+            max_age: None,
+            volume_cap: Some(100),
+        },
+    );
+    client.set_promotion(
+        &admin,
+        &token,
+        &Some(1),
+        &Promotion {
+            max_age: None,
+            volume_cap: Some(5_000),
+        },
+    );
+
+    assert_eq!(
+        client
+            .get_promotion_allowance(&1, &token)
+            .unwrap()
+            .volume_remaining,
+        Some(5_000)
+    );
+    assert_eq!(
+        client
+            .get_promotion_allowance(&2, &token)
+            .unwrap()
+            .volume_remaining,
+        Some(100)
+    );
+}
+
+#[test]
+fn test_merchant_promotion_usage_not_charged_to_global() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    client.set_promotion(
+        &admin,
+        &token,
+        &None,
+        &Promotion {
+            max_age: None,
+            volume_cap: Some(1_000),
+        },
+    );
+    client.set_promotion(
+        &admin,
+        &token,
+        &Some(1),
+        &Promotion {
+            max_age: None,
+            volume_cap: Some(5_000),
+        },
+    );
+
+    pay(&env, &client, &merchant, &token, 2_000);
+    client.remove_promotion(&admin, &token, &Some(1));
+
+    // The global promotion is untouched by volume waived under the merchant one
+    assert_eq!(
+        client
+            .get_promotion_allowance(&1, &token)
+            .unwrap()
+            .volume_remaining,
+        Some(1_000)
+    );
+}
+
+#[test]
+fn test_replacing_promotion_resets_usage() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    let promotion = Promotion {
+        max_age: None,
+        volume_cap: Some(1_000),
+    };
+    client.set_promotion(&admin, &token, &None, &promotion);
+
+    pay(&env, &client, &merchant, &token, 1_000);
+    assert_eq!(client.get_promotion_allowance(&1, &token), None);
+
+    client.set_promotion(&admin, &token, &None, &promotion);
+    assert_eq!(
+        client
+            .get_promotion_allowance(&1, &token)
+            .unwrap()
+            .volume_remaining,
+        Some(1_000)
+    );
+}
+
+#[test]
+fn test_remove_promotion() {
+    let (env, client, _contract_id, admin, token) = setup_test_with_payment();
+    let (_merchant, _merchant_account) = register_merchant_with_account(&env, &client);
+
+    client.set_promotion(
+        &admin,
+        &token,
+        &None,
+        &Promotion {
+            max_age: Some(1_000),
+            volume_cap: None,
+        },
+    );
+    client.remove_promotion(&admin, &token, &None);
+
+    assert_eq!(client.get_promotion_allowance(&1, &token), None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #22)")]
+fn test_remove_missing_promotion() {
+    let (_env, client, _contract_id, admin, token) = setup_test_with_payment();
+
+    client.remove_promotion(&admin, &token, &None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_unbounded_promotion_rejected() {
+    let (_env, client, _contract_id, admin, token) = setup_test_with_payment();
+
+    client.set_promotion(
+        &admin,
+        &token,
+        &None,
+        &Promotion {
+            max_age: None,
+            volume_cap: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_set_promotion_non_admin() {
+    let (env, client, _contract_id, _admin, token) = setup_test_with_payment();
+
+    let non_admin = Address::generate(&env);
+    client.set_promotion(
+        &non_admin,
+        &token,
+        &None,
+        &Promotion {
+            max_age: Some(1_000),
+            volume_cap: None,
+        },
+    );
+}
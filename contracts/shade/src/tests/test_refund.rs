@@ -0,0 +1,274 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::types::{DataKey, FeeBearer, InvoiceStatus};
+use account::account::{MerchantAccount, MerchantAccountClient};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, Env, String};
+
+fn setup_test_with_payment() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let shade_contract_id = env.register(Shade, ());
+    let shade_client = ShadeClient::new(&env, &shade_contract_id);
+
+    let admin = Address::generate(&env);
+    shade_client.initialize(&admin);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin.clone());
+
+    shade_client.add_accepted_token(&admin, &token.address());
+
+    // Set fee to 500 bps (5%)
+    shade_client.set_fee(&admin, &token.address(), &500);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+
+    (env, shade_client, shade_contract_id, admin, token.address())
+}
+
+fn register_merchant_with_account(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    shade_contract_id: &Address,
+    admin: &Address,
+) -> (Address, Address) {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+
+    let merchant_account_id = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(env, &merchant_account_id).initialize(
+        &merchant,
+        shade_contract_id,
+        &1_u64,
+    );
+    client.approve_merchant_account(admin, &1, &merchant_account_id);
+
+    (merchant, merchant_account_id)
+}
+
+fn create_and_pay_invoice(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+    payer_funds: i128,
+) -> (Address, u64) {
+    let description = String::from_str(env, "Refundable");
+    let invoice_id = client.create_invoice(merchant, &description, &amount, token);
+
+    let customer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&customer, &payer_funds);
+    client.pay_invoice(&customer, &invoice_id);
+
+    (customer, invoice_id)
+}
+
+#[test]
+fn test_pay_then_full_refund() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) =
+        register_merchant_with_account(&env, &client, &shade_contract_id, &admin);
+
+    let (customer, invoice_id) =
+        create_and_pay_invoice(&env, &client, &merchant, &token, 1000, 1000);
+
+    // The merchant tops up the account to cover the fee it absorbed
+    token::StellarAssetClient::new(&env, &token).mint(&merchant_account, &50);
+
+    client.refund_invoice(&merchant, &invoice_id);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&customer), 1000);
+    assert_eq!(token_client.balance(&merchant_account), 0);
+    assert_eq!(token_client.balance(&shade_contract_id), 50);
+
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Refunded);
+    assert_eq!(invoice.amount_refunded, 1000);
+}
+
+#[test]
+fn test_pay_then_partial_refunds() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) =
+        register_merchant_with_account(&env, &client, &shade_contract_id, &admin);
+
+    let (customer, invoice_id) =
+        create_and_pay_invoice(&env, &client, &merchant, &token, 1000, 1000);
+
+    client.refund_invoice_partial(&merchant, &invoice_id, &300);
+
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyRefunded);
+    assert_eq!(invoice.amount_refunded, 300);
+
+    client.refund_invoice_partial(&merchant, &invoice_id, &200);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&customer), 500);
+    assert_eq!(token_client.balance(&merchant_account), 450);
+    assert_eq!(client.get_invoice(&invoice_id).amount_refunded, 500);
+}
+
+#[test]
+fn test_payer_pays_fee_then_refund_returns_invoice_amount() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, merchant_account) =
+        register_merchant_with_account(&env, &client, &shade_contract_id, &admin);
+    client.set_fee_bearer(&merchant, &FeeBearer::Payer);
+
+    let (customer, invoice_id) =
+        create_and_pay_invoice(&env, &client, &merchant, &token, 1000, 1050);
+
+    client.refund_invoice(&merchant, &invoice_id);
+
+    // The invoice amount is returned; the fee stays with the protocol
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&customer), 1000);
+    assert_eq!(token_client.balance(&merchant_account), 0);
+    assert_eq!(token_client.balance(&shade_contract_id), 50);
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Refunded
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_refund_more_than_paid() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+    let (merchant, _merchant_account) =
+        register_merchant_with_account(&env, &client, &shade_contract_id, &admin);
+
+    let (_customer, invoice_id) =
+        create_and_pay_invoice(&env, &client, &merchant, &token, 1000, 1000);
+
+    client.refund_invoice_partial(&merchant, &invoice_id, &1001);
+}
+
+#[test]
+fn test_migrate_legacy_merchant_accounts() {
+    let (env, client, shade_contract_id, admin, token) = setup_test_with_payment();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    let merchant_account_id = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(&env, &merchant_account_id).initialize(
+        &merchant,
+        &shade_contract_id,
+        &1_u64,
+    );
+
+    // Account registered under the legacy key only
+    env.as_contract(&shade_contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::MerchantBalance(merchant.clone()),
+            &merchant_account_id,
+        );
+    });
+
+    assert_eq!(client.migrate_merchant_accounts(&admin, &1, &10), 1);
+    assert_eq!(client.get_merchant_account(&1), merchant_account_id);
+
+    env.as_contract(&shade_contract_id, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::MerchantBalance(merchant.clone())));
+    });
+
+    // Payments and refunds both route through the migrated account
+    let (customer, invoice_id) =
+        create_and_pay_invoice(&env, &client, &merchant, &token, 1000, 1000);
+    client.refund_invoice_partial(&merchant, &invoice_id, &950);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&customer), 950);
+    assert_eq!(token_client.balance(&merchant_account_id), 0);
+
+    // Running again is a no-op
+    assert_eq!(client.migrate_merchant_accounts(&admin, &1, &10), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_migrate_merchant_accounts_non_admin() {
+    let (env, client, _shade_contract_id, _admin, _token) = setup_test_with_payment();
+
+    let non_admin = Address::generate(&env);
+    client.migrate_merchant_accounts(&non_admin, &1, &10);
+}
+
+fn register_legacy_account(
+    env: &Env,
+    client: &ShadeClient<'_>,
+    shade_contract_id: &Address,
+    merchant_id: u64,
+) -> (Address, Address) {
+    let merchant = Address::generate(env);
+    client.register_merchant(&merchant);
+
+    let merchant_account_id = env.register(MerchantAccount, ());
+    MerchantAccountClient::new(env, &merchant_account_id).initialize(
+        &merchant,
+        shade_contract_id,
+        &merchant_id,
+    );
+    env.as_contract(shade_contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::MerchantBalance(merchant.clone()),
+            &merchant_account_id,
+        );
+    });
+
+    (merchant, merchant_account_id)
+}
+
+#[test]
+fn test_migrate_merchant_accounts_in_batches() {
+    let (env, client, shade_contract_id, admin, _token) = setup_test_with_payment();
+    let (_merchant, first_account) = register_legacy_account(&env, &client, &shade_contract_id, 1);
+    let (_other, second_account) = register_legacy_account(&env, &client, &shade_contract_id, 2);
+
+    assert_eq!(client.migrate_merchant_accounts(&admin, &1, &1), 1);
+    assert_eq!(client.get_merchant_account(&1), first_account);
+    assert!(client.try_get_merchant_account(&2).is_err());
+
+    assert_eq!(client.migrate_merchant_accounts(&admin, &2, &1), 1);
+    assert_eq!(client.get_merchant_account(&2), second_account);
+
+    // Past the last merchant there is nothing to do
+    assert_eq!(client.migrate_merchant_accounts(&admin, &3, &10), 0);
+}
+
+#[test]
+fn test_migrate_skips_unmanaged_legacy_account() {
+    let (env, client, shade_contract_id, admin, _token) = setup_test_with_payment();
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+
+    // A plain address cannot be managed by Shade
+    let legacy_account = Address::generate(&env);
+    env.as_contract(&shade_contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantBalance(merchant.clone()), &legacy_account);
+    });
+
+    assert_eq!(client.migrate_merchant_accounts(&admin, &1, &10), 0);
+    assert!(client.try_get_merchant_account(&1).is_err());
+
+    env.as_contract(&shade_contract_id, || {
+        assert!(env
+            .storage()
+            .persistent()
+            .has(&DataKey::MerchantBalance(merchant.clone())));
+    });
+}
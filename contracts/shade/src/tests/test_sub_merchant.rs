@@ -0,0 +1,382 @@
+#![cfg(test)]
+
+use crate::components::admin::FEE_CHANGE_DELAY;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{DataKey, InvoiceStatus, Role};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, vec, Address, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let platform = Address::generate(&env);
+    client.register_merchant(&platform);
+    client.deploy_merchant_account(&platform);
+
+    (env, client, admin, platform, token)
+}
+
+fn register_seller(env: &Env, client: &ShadeClient, platform: &Address) -> (Address, u64) {
+    let seller = Address::generate(env);
+    let seller_id = client.register_sub_merchant(platform, &seller);
+    client.deploy_merchant_account(&seller);
+    (seller, seller_id)
+}
+
+fn set_commission(env: &Env, client: &ShadeClient, platform: &Address, commission_bps: i128) {
+    client.set_platform_commission(platform, &commission_bps);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + FEE_CHANGE_DELAY);
+}
+
+fn pay(env: &Env, client: &ShadeClient, token: &Address, invoice_id: u64) {
+    let payer = Address::generate(env);
+    let amount = client.get_invoice(&invoice_id).amount;
+    token::StellarAssetClient::new(env, token).mint(&payer, &amount);
+    client.pay_invoice(&payer, &invoice_id);
+}
+
+fn balance(env: &Env, token: &Address, owner: &Address) -> i128 {
+    token::TokenClient::new(env, token).balance(owner)
+}
+
+#[test]
+fn test_register_sub_merchant() {
+    let (env, client, _admin, platform, _token) = setup_test();
+
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    let (_, other_id) = register_seller(&env, &client, &platform);
+
+    assert_eq!(seller_id, 2);
+    assert_eq!(client.get_merchant_by_address(&seller).id, seller_id);
+    assert_eq!(client.get_parent_merchant(&seller_id), Some(1));
+    assert_eq!(client.get_parent_merchant(&1), None);
+    assert_eq!(
+        client.get_sub_merchants(&1),
+        vec![&env, seller_id, other_id]
+    );
+    assert_ne!(
+        client.get_merchant_account(&seller_id),
+        client.get_merchant_account(&1)
+    );
+}
+
+#[test]
+fn test_platform_invoices_for_sub_merchant() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (_, seller_id) = register_seller(&env, &client, &platform);
+
+    let invoice_id = client.create_sub_merchant_invoice(
+        &platform,
+        &seller_id,
+        &String::from_str(&env, "Handmade mug"),
+        &1_000,
+        &token,
+    );
+
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.merchant_id, seller_id);
+    assert_eq!(invoice.status, InvoiceStatus::Pending);
+}
+
+#[test]
+fn test_platform_commission_on_payment() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+
+    set_commission(&env, &client, &platform, 1_000);
+    assert_eq!(client.get_platform_commission(&1), 1_000);
+
+    let invoice_id = client.create_invoice(&seller, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 100);
+    assert_eq!(
+        balance(&env, &token, &client.get_merchant_account(&seller_id)),
+        900
+    );
+}
+
+#[test]
+fn test_commission_increase_is_delayed() {
+    let (env, client, _admin, platform, _token) = setup_test();
+
+    env.ledger().set_timestamp(1_000);
+    client.set_platform_commission(&platform, &1_000);
+
+    assert_eq!(client.get_platform_commission(&1), 0);
+    let pending = client.get_pending_platform_commission(&1).unwrap();
+    assert_eq!(pending.fee, 1_000);
+    assert_eq!(pending.effective_at, 1_000 + FEE_CHANGE_DELAY);
+
+    env.ledger().set_timestamp(1_000 + FEE_CHANGE_DELAY);
+    assert_eq!(client.get_platform_commission(&1), 1_000);
+    assert_eq!(client.get_pending_platform_commission(&1), None);
+}
+
+#[test]
+fn test_commission_decrease_applies_immediately() {
+    let (env, client, _admin, platform, _token) = setup_test();
+    set_commission(&env, &client, &platform, 1_000);
+
+    client.set_platform_commission(&platform, &2_000);
+    client.set_platform_commission(&platform, &500);
+
+    assert_eq!(client.get_platform_commission(&1), 500);
+    assert_eq!(client.get_pending_platform_commission(&1), None);
+}
+
+#[test]
+fn test_refund_returns_commission_from_platform() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    set_commission(&env, &client, &platform, 1_000);
+
+    let invoice_id = client.create_invoice(&seller, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+    let payer = client.get_invoice(&invoice_id).payer.unwrap();
+    let platform_account = client.get_merchant_account(&1);
+    let seller_account = client.get_merchant_account(&seller_id);
+
+    client.refund_invoice_partial(&seller, &invoice_id, &250);
+    assert_eq!(balance(&env, &token, &platform_account), 75);
+    assert_eq!(balance(&env, &token, &seller_account), 675);
+
+    client.refund_invoice_partial(&seller, &invoice_id, &750);
+    assert_eq!(balance(&env, &token, &platform_account), 0);
+    assert_eq!(balance(&env, &token, &seller_account), 0);
+    assert_eq!(balance(&env, &token, &payer), 1_000);
+}
+
+#[test]
+fn test_no_commission_for_deactivated_platform() {
+    let (env, client, admin, platform, token) = setup_test();
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    set_commission(&env, &client, &platform, 1_000);
+
+    client.set_merchant_status(&admin, &1, &false);
+
+    let invoice_id = client.create_invoice(&seller, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 0);
+    assert_eq!(
+        balance(&env, &token, &client.get_merchant_account(&seller_id)),
+        1_000
+    );
+}
+
+#[test]
+fn test_refund_after_platform_deactivated() {
+    let (env, client, admin, platform, token) = setup_test();
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    set_commission(&env, &client, &platform, 1_000);
+
+    let invoice_id = client.create_invoice(&seller, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+    let payer = client.get_invoice(&invoice_id).payer.unwrap();
+
+    // The platform's account is restricted, so the seller covers the
+    // commission as well
+    client.set_merchant_status(&admin, &1, &false);
+    let seller_account = client.get_merchant_account(&seller_id);
+    token::StellarAssetClient::new(&env, &token).mint(&seller_account, &100);
+
+    client.refund_invoice(&seller, &invoice_id);
+
+    assert_eq!(balance(&env, &token, &payer), 1_000);
+    assert_eq!(balance(&env, &token, &seller_account), 0);
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #50)")]
+fn test_close_platform_with_sub_merchants() {
+    let (env, client, _admin, platform, _token) = setup_test();
+    register_seller(&env, &client, &platform);
+
+    client.close_merchant(&platform);
+}
+
+#[test]
+fn test_remove_sub_merchant() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    set_commission(&env, &client, &platform, 1_000);
+
+    client.remove_sub_merchant(&platform, &seller_id);
+    assert_eq!(client.get_parent_merchant(&seller_id), None);
+    assert!(client.get_sub_merchants(&1).is_empty());
+
+    client.close_merchant(&platform);
+    assert!(client.is_merchant_closed(&1));
+
+    let invoice_id = client.create_invoice(&seller, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+    assert_eq!(
+        balance(&env, &token, &client.get_merchant_account(&seller_id)),
+        1_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_remove_other_platforms_sub_merchant() {
+    let (env, client, _admin, platform, _token) = setup_test();
+    let (_, seller_id) = register_seller(&env, &client, &platform);
+
+    let other_platform = Address::generate(&env);
+    client.register_merchant(&other_platform);
+    client.remove_sub_merchant(&other_platform, &seller_id);
+}
+
+#[test]
+fn test_no_commission_for_independent_merchant() {
+    let (env, client, _admin, platform, token) = setup_test();
+    set_commission(&env, &client, &platform, 1_000);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    let invoice_id =
+        client.create_invoice(&merchant, &String::from_str(&env, "Mug"), &1_000, &token);
+    pay(&env, &client, &token, invoice_id);
+
+    assert_eq!(
+        balance(&env, &token, &client.get_merchant_account(&2)),
+        1_000
+    );
+    assert_eq!(balance(&env, &token, &client.get_merchant_account(&1)), 0);
+}
+
+#[test]
+fn test_platform_stats_aggregate_sub_merchants() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (_, first_id) = register_seller(&env, &client, &platform);
+    let (_, second_id) = register_seller(&env, &client, &platform);
+
+    let description = String::from_str(&env, "Order");
+    let paid =
+        client.create_sub_merchant_invoice(&platform, &first_id, &description, &1_000, &token);
+    client.create_sub_merchant_invoice(&platform, &second_id, &description, &500, &token);
+    let other =
+        client.create_sub_merchant_invoice(&platform, &second_id, &description, &250, &token);
+    pay(&env, &client, &token, paid);
+    pay(&env, &client, &token, other);
+
+    let stats = client.get_platform_stats(&1);
+    assert_eq!(stats.invoice_count, 3);
+    assert_eq!(stats.pending_count, 1);
+    assert_eq!(stats.paid_count, 2);
+    assert_eq!(stats.volumes.len(), 1);
+    assert_eq!(stats.volumes.get(0).unwrap().gross_volume, 1_250);
+    assert!(stats.first_payment_at.is_some());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #1)")]
+fn test_invoice_for_other_platforms_seller() {
+    let (env, client, _admin, platform, token) = setup_test();
+    let (_, seller_id) = register_seller(&env, &client, &platform);
+
+    let other_platform = Address::generate(&env);
+    client.register_merchant(&other_platform);
+
+    client.create_sub_merchant_invoice(
+        &other_platform,
+        &seller_id,
+        &String::from_str(&env, "Order"),
+        &1_000,
+        &token,
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #31)")]
+fn test_unapproved_platform_cannot_bypass_registration_approval() {
+    let (env, client, admin, platform, _token) = setup_test();
+    client.set_registration_approval(&admin, &true);
+
+    client.register_sub_merchant(&platform, &Address::generate(&env));
+}
+
+#[test]
+fn test_approved_platform_registers_while_approval_required() {
+    let (env, client, admin, platform, _token) = setup_test();
+    client.set_registration_approval(&admin, &true);
+
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &operator, &Role::Operator);
+    client.set_platform_approval(&operator, &1, &true);
+    assert!(client.is_approved_platform(&1));
+
+    let (seller, seller_id) = register_seller(&env, &client, &platform);
+    assert_eq!(client.get_merchant_by_address(&seller).id, seller_id);
+
+    client.set_platform_approval(&operator, &1, &false);
+    assert!(client
+        .try_register_sub_merchant(&platform, &Address::generate(&env))
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #45)")]
+fn test_sub_merchant_cannot_register_sub_merchants() {
+    let (env, client, _admin, platform, _token) = setup_test();
+    let (seller, _) = register_seller(&env, &client, &platform);
+
+    client.register_sub_merchant(&seller, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_commission_above_100_percent() {
+    let (_env, client, _admin, platform, _token) = setup_test();
+    client.set_platform_commission(&platform, &10_001);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
+fn test_commission_requires_platform_account() {
+    let (env, client, _admin, _platform, _token) = setup_test();
+
+    let platform = Address::generate(&env);
+    client.register_merchant(&platform);
+    client.set_platform_commission(&platform, &500);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #24)")]
+fn test_commission_requires_managed_platform_account() {
+    let (env, client, _admin, _platform, _token) = setup_test();
+
+    let platform = Address::generate(&env);
+    client.register_merchant(&platform);
+
+    // Commission could not be returned from a legacy plain-address account
+    let legacy_account = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantAccount(2), &legacy_account);
+    });
+
+    client.set_platform_commission(&platform, &500);
+}
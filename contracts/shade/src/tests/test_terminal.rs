@@ -0,0 +1,210 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::components::terminal::MAX_TERMINAL_KEYS;
+use crate::shade::{Shade, ShadeClient};
+use crate::tests::ACCOUNT_WASM;
+use crate::types::{InvoiceStatus, SignedInvoice};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{token, Address, BytesN, Env, String};
+
+fn setup_test() -> (Env, ShadeClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Shade, ());
+    let client = ShadeClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(ACCOUNT_WASM);
+    client.set_account_wasm_hash(&admin, &wasm_hash);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_accepted_token(&admin, &token);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(&merchant);
+    client.deploy_merchant_account(&merchant);
+
+    (env, client, contract_id, merchant, token)
+}
+
+fn terminal_key(env: &Env, seed: u8) -> (SigningKey, BytesN<32>) {
+    let signing_key = SigningKey::from_bytes(&[seed; 32]);
+    let public_key = BytesN::from_array(env, &signing_key.verifying_key().to_bytes());
+    (signing_key, public_key)
+}
+
+fn signed_invoice(env: &Env, token: &Address, nonce: u64, expires_at: u64) -> SignedInvoice {
+    SignedInvoice {
+        merchant_id: 1,
+        description: String::from_str(env, "Coffee"),
+        amount: 1000,
+        token: token.clone(),
+        nonce,
+        expires_at,
+    }
+}
+
+fn sign(
+    env: &Env,
+    contract_id: &Address,
+    signing_key: &SigningKey,
+    invoice: &SignedInvoice,
+) -> BytesN<64> {
+    let message = (contract_id.clone(), invoice.clone()).to_xdr(env);
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(env, &signing_key.sign(&message).to_bytes())
+}
+
+fn funded_payer(env: &Env, token: &Address, amount: i128) -> Address {
+    let payer = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&payer, &amount);
+    payer
+}
+
+#[test]
+fn test_pay_signed_invoice() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+    client.add_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    let invoice_id = client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+
+    let stored = client.get_invoice(&invoice_id);
+    assert_eq!(stored.status, InvoiceStatus::Paid);
+    assert_eq!(stored.merchant_id, 1);
+    assert_eq!(stored.amount, 1000);
+    assert_eq!(stored.payer, Some(payer));
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&client.get_merchant_account(&1)),
+        1000
+    );
+}
+
+#[test]
+fn test_manage_terminal_keys() {
+    let (env, client, _contract_id, merchant, _token) = setup_test();
+    let (_, first_key) = terminal_key(&env, 1);
+    let (_, second_key) = terminal_key(&env, 2);
+
+    client.add_terminal_key(&merchant, &first_key);
+    client.add_terminal_key(&merchant, &second_key);
+    client.add_terminal_key(&merchant, &first_key);
+    assert_eq!(client.get_terminal_keys(&1).len(), 2);
+
+    client.remove_terminal_key(&merchant, &first_key);
+    let keys = client.get_terminal_keys(&1);
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys.get(0).unwrap(), second_key);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #48)")]
+fn test_too_many_terminal_keys() {
+    let (env, client, _contract_id, merchant, _token) = setup_test();
+
+    for seed in 0..=MAX_TERMINAL_KEYS as u8 {
+        let (_, key) = terminal_key(&env, seed);
+        client.add_terminal_key(&merchant, &key);
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #37)")]
+fn test_pay_signed_invoice_replay() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+    client.add_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 7, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 2000);
+
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #36)")]
+fn test_pay_signed_invoice_expired() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+    client.add_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    env.ledger().set_timestamp(101);
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #35)")]
+fn test_pay_signed_invoice_unknown_key() {
+    let (env, client, contract_id, _merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_pay_signed_invoice_tampered_amount() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+    client.add_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    let mut tampered = invoice.clone();
+    tampered.amount = 1;
+    client.pay_signed_invoice(&payer, &tampered, &public_key, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_pay_signed_invoice_signed_by_other_key() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (_, public_key) = terminal_key(&env, 1);
+    let (other_signing_key, _) = terminal_key(&env, 2);
+    client.add_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &other_signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #35)")]
+fn test_removed_terminal_key_rejected() {
+    let (env, client, contract_id, merchant, token) = setup_test();
+    let (signing_key, public_key) = terminal_key(&env, 1);
+    client.add_terminal_key(&merchant, &public_key);
+    client.remove_terminal_key(&merchant, &public_key);
+
+    let invoice = signed_invoice(&env, &token, 1, 100);
+    let signature = sign(&env, &contract_id, &signing_key, &invoice);
+    let payer = funded_payer(&env, &token, 1000);
+
+    client.pay_signed_invoice(&payer, &invoice, &public_key, &signature);
+}
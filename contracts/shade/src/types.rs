@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 #[contracttype]
 pub enum DataKey {
@@ -14,6 +14,7 @@ pub enum DataKey {
     MerchantId(Address),
     TokenFee(Address),
     MerchantTokens,
+    // Legacy merchant account key, superseded by `MerchantAccount`
     MerchantBalance(Address),
     MerchantAccount(u64),
     Invoice(u64),
@@ -21,6 +22,65 @@ pub enum DataKey {
     ReentrancyStatus,
     AccountWasmHash,
     Role(Address, Role),
+    MerchantFeeBearer(u64),
+    PendingFee(Address),
+    FeeRounding(Address),
+    GlobalPromotion(Address),
+    MerchantPromotion(u64, Address),
+    PromotionCount,
+    PromotionUsage(u64, u64),
+    DeployedAccount(Address),
+    MerchantProfile(u64),
+    PendingMerchantAddress(u64),
+    MerchantTier(u64),
+    TierLimits(VerificationTier, Address),
+    MerchantVolume(u64, Address),
+    RegistrationApprovalRequired,
+    MerchantApplication(Address),
+    PendingApplicationCount,
+    PendingApplication(u32),
+    PendingApplicationIndex(Address),
+    Staff(Address),
+    TerminalKeys(u64),
+    TerminalNonce(BytesN<32>, u64),
+    MerchantKeyHistory(u64),
+    MerchantTokenAllowlist(u64),
+    MerchantStats(u64),
+    MerchantPause(u64),
+    StakeRequirement,
+    MerchantStake(u64),
+    ParentMerchant(u64),
+    SubMerchants(u64),
+    PlatformCommission(u64),
+    InvoiceSplit(u64),
+}
+
+/// Merchant-scoped storage keys. `DataKey` is at the 50 case limit of a
+/// contract type, so keys added since then live here.
+#[contracttype]
+pub enum MerchantDataKey {
+    Closed(u64),
+    PendingInvoices(u64),
+    PendingCommission(u64),
+    ApprovedPlatform(u64),
+    InvoicesIndexed(u64),
+    InvoiceIndexCursor(u64),
+}
+
+/// Invoice-scoped storage keys, see `MerchantDataKey`.
+#[contracttype]
+pub enum InvoiceDataKey {
+    Fee(u64),
+    StaffRefunded(u64, Address),
+    Commission(u64),
+    SplitPayouts(u64),
+    SplitReleased(u64),
+}
+
+/// Token-scoped storage keys, see `MerchantDataKey`.
+#[contracttype]
+pub enum TokenDataKey {
+    PendingFeeRounding(Address),
 }
 
 #[contracttype]
@@ -30,6 +90,34 @@ pub struct ContractInfo {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFee {
+    pub fee: i128,
+    pub effective_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Promotion {
+    pub max_age: Option<u64>,
+    pub volume_cap: Option<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromotionRecord {
+    pub id: u64,
+    pub promotion: Promotion,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromotionAllowance {
+    pub time_remaining: Option<u64>,
+    pub volume_remaining: Option<i128>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Merchant {
@@ -40,6 +128,25 @@ pub struct Merchant {
     pub date_registered: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantProfile {
+    pub name: String,
+    pub website: String,
+    pub support_email_hash: BytesN<32>,
+    pub logo_uri: String,
+    pub category: String,
+    pub country_code: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantApplication {
+    pub applicant: Address,
+    pub profile: MerchantProfile,
+    pub date_submitted: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Invoice {
@@ -55,6 +162,109 @@ pub struct Invoice {
     pub amount_refunded: i128,
 }
 
+/// Fee terms of an invoice, kept under their own key so that invoices
+/// stored before fee bearers existed still decode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceFee {
+    pub fee_bearer: FeeBearer,
+    pub fee_amount: i128,
+}
+
+/// Platform commission taken from a sub-merchant's invoice payment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceCommission {
+    pub platform_id: u64,
+    pub amount: i128,
+}
+
+/// Invoice issued off-chain by a point-of-sale terminal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedInvoice {
+    pub merchant_id: u64,
+    pub description: String,
+    pub amount: i128,
+    pub token: Address,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantKeyRecord {
+    pub key: BytesN<32>,
+    pub version: u32,
+    pub activated_at: u64,
+    pub revoked_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitRecipient {
+    pub recipient: Address,
+    pub share_bps: i128,
+}
+
+/// A split recipient's share of a paid invoice, held by Shade until the
+/// refund window closes. `refunded` is the part already returned to the
+/// payer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitPayout {
+    pub recipient: Address,
+    pub amount: i128,
+    pub refunded: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeRequirement {
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantStake {
+    pub token: Address,
+    pub amount: i128,
+    pub slashed: i128,
+    pub unlock_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantPause {
+    pub paused_at: u64,
+    pub resume_at: Option<u64>,
+    pub message: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenVolume {
+    pub token: Address,
+    pub gross_volume: i128,
+    pub fees_paid: i128,
+    pub refunded: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantStats {
+    pub invoice_count: u64,
+    pub pending_count: u64,
+    pub paid_count: u64,
+    pub cancelled_count: u64,
+    pub refunded_count: u64,
+    pub partially_refunded_count: u64,
+    pub first_payment_at: Option<u64>,
+    pub last_payment_at: Option<u64>,
+    pub volumes: Vec<TokenVolume>,
+}
+
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -66,11 +276,74 @@ pub enum InvoiceStatus {
     PartiallyRefunded = 4,
 }
 
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FeeBearer {
+    Merchant = 0,
+    Payer = 1,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FeeRounding {
+    Floor = 0,
+    Ceil = 1,
+    HalfEven = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeRounding {
+    pub rounding: FeeRounding,
+    pub effective_at: u64,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VerificationTier {
+    Unverified = 0,
+    Basic = 1,
+    Full = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierLimits {
+    pub max_invoice_amount: Option<i128>,
+    pub max_volume_30d: Option<i128>,
+}
+
+/// Paid volume of the last `LIMIT_WINDOW_DAYS` days in one entry, with the
+/// bucket of each day at index `day % LIMIT_WINDOW_DAYS`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeWindow {
+    pub last_day: u64,
+    pub daily: Vec<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimitHeadroom {
+    pub tier: VerificationTier,
+    pub max_invoice_amount: Option<i128>,
+    pub volume_remaining: Option<i128>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MerchantFilter {
     pub is_active: Option<bool>,
     pub is_verified: Option<bool>,
+    pub category: Option<String>,
+    pub country_code: Option<String>,
+    pub registered_from: Option<u64>,
+    pub registered_until: Option<u64>,
+    pub has_account: Option<bool>,
+    pub has_key: Option<bool>,
 }
 
 #[contracttype]
@@ -82,6 +355,25 @@ pub struct InvoiceFilter {
     pub max_amount: Option<u128>,
 }
 
+/// Merchant-scoped staff roles, granted by the merchant itself and
+/// independent of the contract-wide `Role`.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MerchantRole {
+    Cashier = 0,
+    Accountant = 1,
+    RefundManager = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffMember {
+    pub merchant_id: u64,
+    pub roles: Vec<MerchantRole>,
+    pub refund_limit: Option<i128>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Role {